@@ -1,41 +1,497 @@
 #![cfg(not(target_arch = "bpf"))]
 
 use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use std::str::FromStr;
+use xft_types::{active_operator_role, normalize_title, title_seed, xft_account_address};
 
 declare_id!("Admin111111111111111111111111111111111111111");
 
 #[program]
 pub mod admin_xft {
     use super::*;
-    pub fn initialize(ctx: Context<Initialize>, platform_xft_in: u64, payout_address: Pubkey, mint_fee_per_year: u64, marketplace_fee_sol: u64, marketplace_fee_dexsta: u64, dexsta_address: Pubkey) -> Result<()> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(ctx: Context<Initialize>, platform_xft_in: u64, payout_address: Pubkey, mint_fee_per_year: u64, marketplace_fee_sol: u64, marketplace_fee_dexsta: u64, dexsta_address: Pubkey, fee_change_delay: i64) -> Result<()> {
+        require!(fee_change_delay >= 0, AdminError::InvalidDelay);
         let admin = &mut ctx.accounts.admin;
         admin.platform_xft_in = platform_xft_in;
         admin.payout_address = payout_address;
+        admin.payout_recipients = vec![PayoutRecipient { address: payout_address, weight_bps: 10000 }];
         admin.mint_fee_per_year = mint_fee_per_year;
         admin.marketplace_fee_sol = marketplace_fee_sol;
         admin.marketplace_fee_dexsta = marketplace_fee_dexsta;
         admin.dexsta_address = dexsta_address;
         admin.bump = ctx.bumps.admin;
+        admin.fee_change_delay = fee_change_delay;
+        admin.version = AdminXFT::VERSION;
         Ok(())
     }
-    pub fn set_fees(ctx: Context<SetFees>, mint_fee_per_year: u64, marketplace_fee_sol: u64, marketplace_fee_dexsta: u64) -> Result<()> {
-        let admin = &mut ctx.accounts.admin;
-        require!(is_super_operator(ctx.accounts.admin_operator.as_ref(), admin.platform_xft_in), AdminError::Unauthorized);
-        admin.mint_fee_per_year = mint_fee_per_year;
-        admin.marketplace_fee_sol = marketplace_fee_sol;
-        admin.marketplace_fee_dexsta = marketplace_fee_dexsta;
+
+    /// Grows an `admin_xft` PDA written by an older program version to `AdminXFT::LEN`
+    /// and fills in defaults for the fields added since. New fields are always appended,
+    /// so the realloc'd (zeroed) tail decodes cleanly before defaults are applied.
+    pub fn migrate_admin(ctx: Context<MigrateAdmin>) -> Result<()> {
+        let admin_info = ctx.accounts.admin.to_account_info();
+        require!(*admin_info.owner == crate::ID, AdminError::InvalidAdminAccount);
+        // platform_xft_in leads every layout, so the caller is checked before anything is
+        // reallocated or paid for
+        let platform_xft_in = {
+            let data = admin_info.try_borrow_data()?;
+            let bytes = data.get(8..16).ok_or(AdminError::InvalidAdminAccount)?;
+            u64::from_le_bytes(bytes.try_into().unwrap())
+        };
+        require_super_operator(
+            &ctx.accounts.admin_operator.key(),
+            &ctx.accounts.label_account,
+            &ctx.accounts.operator_account,
+            platform_xft_in,
+        )?;
+
+        if admin_info.data_len() < AdminXFT::LEN {
+            let rent_needed = Rent::get()?
+                .minimum_balance(AdminXFT::LEN)
+                .saturating_sub(admin_info.lamports());
+            if rent_needed > 0 {
+                let cpi_ctx = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.admin_operator.to_account_info(),
+                        to: admin_info.clone(),
+                    },
+                );
+                anchor_lang::system_program::transfer(cpi_ctx, rent_needed)?;
+            }
+            admin_info.resize(AdminXFT::LEN)?;
+        }
+
+        let mut admin = AdminXFT::try_deserialize(&mut &admin_info.try_borrow_data()?[..])?;
+        require!(admin.version < AdminXFT::VERSION, AdminError::AlreadyMigrated);
+
+        let from_version = admin.version;
+        // Version 1: fee_change_delay, version
+        if from_version < 1 {
+            admin.fee_change_delay = AdminXFT::DEFAULT_FEE_CHANGE_DELAY;
+        }
+        // Version 2: payout_recipients, the existing payout_address takes the full weight
+        if from_version < 2 {
+            admin.payout_recipients = vec![PayoutRecipient { address: admin.payout_address, weight_bps: 10000 }];
+        }
+        admin.version = AdminXFT::VERSION;
+        admin.try_serialize(&mut &mut admin_info.try_borrow_mut_data()?[..])?;
+
+        emit!(AdminMigrated {
+            from_version,
+            to_version: admin.version,
+            migrated_by: ctx.accounts.admin_operator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
-    pub fn set_payout_address(ctx: Context<SetPayoutAddress>, payout_address: Pubkey) -> Result<()> {
+
+    /// Fee and payout changes are timelocked: a super operator proposes new values,
+    /// and anyone can execute them once `eta` (now + `fee_change_delay`) has passed.
+    /// The delay itself is part of the proposal so it cannot be shortened instantly.
+    /// Each proposal lives at its own `proposal_id` PDA, so several can be pending at once
+    /// and are executed or cancelled by id.
+    /// `payout_recipients` weights must add up to 10000; the first entry is the primary
+    /// recipient, becomes `payout_address` and receives rounding dust on sweeps.
+    #[access_control(ctx.accounts.authorize())]
+    pub fn propose_fee_change(
+        ctx: Context<ProposeFeeChange>,
+        proposal_id: u64,
+        mint_fee_per_year: u64,
+        marketplace_fee_sol: u64,
+        marketplace_fee_dexsta: u64,
+        payout_recipients: Vec<PayoutRecipient>,
+        fee_change_delay: i64,
+    ) -> Result<()> {
+        require!(fee_change_delay >= 0, AdminError::InvalidDelay);
+        validate_payout_recipients(&payout_recipients)?;
+        let payout_address = payout_recipients[0].address;
+        let now = Clock::get()?.unix_timestamp;
+        let eta = now.checked_add(ctx.accounts.admin.fee_change_delay).ok_or(AdminError::InvalidDelay)?;
+
+        let proposal = &mut ctx.accounts.fee_proposal;
+        proposal.proposal_id = proposal_id;
+        proposal.proposer = ctx.accounts.admin_operator.key();
+        proposal.mint_fee_per_year = mint_fee_per_year;
+        proposal.marketplace_fee_sol = marketplace_fee_sol;
+        proposal.marketplace_fee_dexsta = marketplace_fee_dexsta;
+        proposal.payout_address = payout_address;
+        proposal.payout_recipients = payout_recipients.clone();
+        proposal.fee_change_delay = fee_change_delay;
+        proposal.eta = eta;
+        proposal.bump = ctx.bumps.fee_proposal;
+
+        emit!(FeeChangeProposed {
+            proposal_id,
+            proposer: proposal.proposer,
+            mint_fee_per_year,
+            marketplace_fee_sol,
+            marketplace_fee_dexsta,
+            payout_address,
+            payout_recipients,
+            fee_change_delay,
+            eta,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    pub fn execute_fee_change(ctx: Context<ExecuteFeeChange>, proposal_id: u64) -> Result<()> {
+        let proposal = &ctx.accounts.fee_proposal;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= proposal.eta, AdminError::ProposalNotReady);
+
         let admin = &mut ctx.accounts.admin;
-        require!(is_super_operator(ctx.accounts.admin_operator.as_ref(), admin.platform_xft_in), AdminError::Unauthorized);
-        admin.payout_address = payout_address;
+        admin.mint_fee_per_year = proposal.mint_fee_per_year;
+        admin.marketplace_fee_sol = proposal.marketplace_fee_sol;
+        admin.marketplace_fee_dexsta = proposal.marketplace_fee_dexsta;
+        admin.payout_address = proposal.payout_address;
+        admin.payout_recipients = proposal.payout_recipients.clone();
+        admin.fee_change_delay = proposal.fee_change_delay;
+
+        emit!(FeeChangeExecuted {
+            proposal_id,
+            executor: ctx.accounts.executor.key(),
+            mint_fee_per_year: proposal.mint_fee_per_year,
+            marketplace_fee_sol: proposal.marketplace_fee_sol,
+            marketplace_fee_dexsta: proposal.marketplace_fee_dexsta,
+            payout_address: proposal.payout_address,
+            payout_recipients: proposal.payout_recipients.clone(),
+            fee_change_delay: proposal.fee_change_delay,
+            timestamp: now,
+        });
         Ok(())
     }
+
+    #[access_control(ctx.accounts.authorize())]
+    pub fn cancel_fee_change(ctx: Context<CancelFeeChange>, proposal_id: u64) -> Result<()> {
+        emit!(FeeChangeCancelled {
+            proposal_id,
+            cancelled_by: ctx.accounts.admin_operator.key(),
+            eta: ctx.accounts.fee_proposal.eta,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
     pub fn get_fees(ctx: Context<GetFees>) -> Result<(u64, u64, u64, Pubkey, Pubkey)> {
         let admin = &ctx.accounts.admin;
         Ok((admin.mint_fee_per_year, admin.marketplace_fee_sol, admin.marketplace_fee_dexsta, admin.dexsta_address, admin.payout_address))
     }
+
+    /// Sets the mint fee schedule for one label type (settings[3] on the minter):
+    /// * fee_per_year: base price per registration year
+    /// * title_length_fees: optional per-year price for short titles, the tier with the
+    ///   smallest max_title_len that still fits the title wins
+    /// * year_discounts: multi-year discount in basis points, the tier with the largest
+    ///   min_years not above the paid years wins
+    #[access_control(ctx.accounts.authorize())]
+    pub fn set_mint_fee_schedule(
+        ctx: Context<SetMintFeeSchedule>,
+        label_type: u64,
+        fee_per_year: u64,
+        title_length_fees: Vec<TitleLengthFee>,
+        year_discounts: Vec<YearDiscount>,
+    ) -> Result<()> {
+        require!(label_type > 0, AdminError::InvalidFeeSchedule);
+        require!(title_length_fees.len() <= MintFeeSchedule::MAX_TIERS, AdminError::InvalidFeeSchedule);
+        require!(year_discounts.len() <= MintFeeSchedule::MAX_TIERS, AdminError::InvalidFeeSchedule);
+        require!(year_discounts.iter().all(|d| d.discount_bps <= 10000), AdminError::InvalidFeeSchedule);
+
+        let schedule = &mut ctx.accounts.fee_schedule;
+        schedule.label_type = label_type;
+        schedule.fee_per_year = fee_per_year;
+        schedule.title_length_fees = title_length_fees;
+        schedule.year_discounts = year_discounts;
+        schedule.bump = ctx.bumps.fee_schedule;
+
+        emit!(MintFeeScheduleSet {
+            label_type,
+            fee_per_year,
+            updated_by: ctx.accounts.admin_operator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Total mint fee for `years` of registration of a label of `label_type` with a
+    /// whitespace-stripped title of `title_len` characters. Falls back to
+    /// `mint_fee_per_year` when the label type's schedule PDA has not been created.
+    pub fn get_mint_fee(ctx: Context<GetMintFee>, label_type: u64, title_len: u64, years: u64) -> Result<u64> {
+        let admin = &ctx.accounts.admin;
+        let fee = match MintFeeSchedule::load(&ctx.accounts.fee_schedule)? {
+            Some(schedule) => {
+                require!(schedule.label_type == label_type, AdminError::InvalidFeeSchedule);
+                schedule.total_fee(title_len, years)
+            }
+            None => admin.mint_fee_per_year.saturating_mul(years),
+        };
+        Ok(fee)
+    }
+
+    /// Creates the SOL fee treasury, seeds = [b"treasury", Pubkey::default()]. Lamports are held by the PDA itself.
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.mint = Pubkey::default();
+        treasury.total_collected = 0;
+        treasury.total_swept = 0;
+        treasury.bump = ctx.bumps.treasury;
+        Ok(())
+    }
+
+    /// Creates the fee treasury for an SPL mint, seeds = [b"treasury", mint], with its
+    /// token account at [b"treasury_vault", mint] owned by the treasury PDA.
+    pub fn initialize_spl_treasury(ctx: Context<InitializeSplTreasury>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.mint = ctx.accounts.mint.key();
+        treasury.total_collected = 0;
+        treasury.total_swept = 0;
+        treasury.bump = ctx.bumps.treasury;
+        Ok(())
+    }
+
+    /// Pays a protocol fee into the treasury and adds it to the running total.
+    /// SOL treasuries take lamports from `payer`; SPL treasuries take tokens from
+    /// `payer_token_account` into `treasury_token_account`.
+    pub fn deposit_fee(ctx: Context<DepositFee>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let treasury_mint = ctx.accounts.treasury.mint;
+        if treasury_mint == Pubkey::default() {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+        } else {
+            let treasury_token_account = ctx.accounts.treasury_token_account.as_ref().ok_or(AdminError::InvalidTreasury)?;
+            let payer_token_account = ctx.accounts.payer_token_account.as_ref().ok_or(AdminError::InvalidTreasury)?;
+            let token_program = ctx.accounts.token_program.as_ref().ok_or(AdminError::InvalidTreasury)?;
+            require!(payer_token_account.mint == treasury_mint, AdminError::InvalidTreasury);
+            let cpi_ctx = CpiContext::new(
+                token_program.to_account_info(),
+                token::Transfer {
+                    from: payer_token_account.to_account_info(),
+                    to: treasury_token_account.to_account_info(),
+                    authority: ctx.accounts.payer.to_account_info(),
+                },
+            );
+            token::transfer(cpi_ctx, amount)?;
+        }
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_collected = treasury.total_collected.saturating_add(amount);
+
+        emit!(FeeDeposited {
+            mint: treasury_mint,
+            payer: ctx.accounts.payer.key(),
+            amount,
+            total_collected: treasury.total_collected,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Splits everything above rent in a treasury between `payout_recipients` by weight,
+    /// with rounding dust going to the primary (first) recipient. Anyone can call this
+    /// since the destinations are fixed by AdminXFT. `remaining_accounts` must hold one
+    /// account per recipient, in order: the wallet for SOL, its token account for SPL.
+    pub fn sweep_fees<'info>(ctx: Context<'_, '_, 'info, 'info, SweepFees<'info>>) -> Result<()> {
+        let treasury_mint = ctx.accounts.treasury.mint;
+        let recipients = ctx.accounts.admin.payout_recipients.clone();
+        require!(!recipients.is_empty(), AdminError::InvalidPayoutRecipients);
+        require!(ctx.remaining_accounts.len() == recipients.len(), AdminError::InvalidPayoutRecipients);
+
+        let amount = if treasury_mint == Pubkey::default() {
+            let treasury_info = ctx.accounts.treasury.to_account_info();
+            let rent_exempt = Rent::get()?.minimum_balance(treasury_info.data_len());
+            treasury_info.lamports().saturating_sub(rent_exempt)
+        } else {
+            ctx.accounts.treasury_token_account.as_ref().ok_or(AdminError::InvalidTreasury)?.amount
+        };
+        let shares = split_by_weight(amount, &recipients);
+
+        let bump = ctx.accounts.treasury.bump;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"treasury", treasury_mint.as_ref(), &[bump]]];
+        for ((recipient, destination), share) in recipients.iter().zip(ctx.remaining_accounts.iter()).zip(shares.iter()) {
+            if treasury_mint == Pubkey::default() {
+                require!(destination.key() == recipient.address, AdminError::InvalidPayoutRecipients);
+                let treasury_info = ctx.accounts.treasury.to_account_info();
+                **treasury_info.try_borrow_mut_lamports()? -= *share;
+                **destination.try_borrow_mut_lamports()? += *share;
+            } else {
+                let destination_token_account = Account::<TokenAccount>::try_from(destination)?;
+                require!(
+                    destination_token_account.owner == recipient.address && destination_token_account.mint == treasury_mint,
+                    AdminError::InvalidPayoutRecipients
+                );
+                let treasury_token_account = ctx.accounts.treasury_token_account.as_ref().ok_or(AdminError::InvalidTreasury)?;
+                let token_program = ctx.accounts.token_program.as_ref().ok_or(AdminError::InvalidTreasury)?;
+                let cpi_ctx = CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    token::Transfer {
+                        from: treasury_token_account.to_account_info(),
+                        to: destination.clone(),
+                        authority: ctx.accounts.treasury.to_account_info(),
+                    },
+                    signer_seeds,
+                );
+                token::transfer(cpi_ctx, *share)?;
+            }
+        }
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_swept = treasury.total_swept.saturating_add(amount);
+
+        emit!(FeesSwept {
+            mint: treasury_mint,
+            recipients: recipients.iter().map(|r| r.address).collect(),
+            amounts: shares,
+            amount,
+            total_swept: treasury.total_swept,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Adds or updates an accepted SPL payment mint:
+    /// * decimals: must match the mint account, guards against registering the wrong mint
+    /// * marketplace_fee_bps: platform fee on market sales paid in this mint
+    /// * mint_fee_rate: token base units charged per 1 SOL of mint fee, 0 = not accepted for minting
+    /// * enabled: disabled mints are rejected by market and minter
+    #[access_control(ctx.accounts.authorize())]
+    pub fn set_payment_mint(
+        ctx: Context<SetPaymentMint>,
+        decimals: u8,
+        marketplace_fee_bps: u64,
+        mint_fee_rate: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        require!(ctx.accounts.mint.decimals == decimals, AdminError::DecimalsMismatch);
+        require!(marketplace_fee_bps <= 10000, AdminError::InvalidFeeBps);
+
+        let entry = &mut ctx.accounts.payment_mint;
+        entry.mint = ctx.accounts.mint.key();
+        entry.decimals = decimals;
+        entry.marketplace_fee_bps = marketplace_fee_bps;
+        entry.mint_fee_rate = mint_fee_rate;
+        entry.enabled = enabled;
+        entry.bump = ctx.bumps.payment_mint;
+
+        emit!(PaymentMintUpdated {
+            mint: entry.mint,
+            decimals,
+            marketplace_fee_bps,
+            mint_fee_rate,
+            enabled,
+            updated_by: ctx.accounts.admin_operator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Blocks `address` from minting, buying, selling or receiving XFTs. Minter and market
+    /// require the (empty) blocklist PDA of every party, so an existing entry rejects them.
+    #[access_control(ctx.accounts.authorize())]
+    pub fn add_to_blocklist(ctx: Context<AddToBlocklist>, address: Pubkey) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let entry = &mut ctx.accounts.blocklist_entry;
+        entry.address = address;
+        entry.added_by = ctx.accounts.admin_operator.key();
+        entry.added_at = now;
+        entry.bump = ctx.bumps.blocklist_entry;
+
+        emit!(AddressBlocked {
+            address,
+            updated_by: entry.added_by,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    #[access_control(ctx.accounts.authorize())]
+    pub fn remove_from_blocklist(ctx: Context<RemoveFromBlocklist>, address: Pubkey) -> Result<()> {
+        emit!(AddressUnblocked {
+            address,
+            updated_by: ctx.accounts.admin_operator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Reserves a label title and/or prices it as premium. `title` is stored in the normalized
+    /// form (xft_types::normalize_title) used by the minter's title lookup:
+    /// * reserved_for: only this wallet may mint the title, Pubkey::default() = anyone
+    /// * premium_fee_per_year: replaces the fee schedule price when > 0
+    #[access_control(ctx.accounts.authorize())]
+    pub fn reserve_title(
+        ctx: Context<ReserveTitle>,
+        title: String,
+        reserved_for: Pubkey,
+        premium_fee_per_year: u64,
+    ) -> Result<()> {
+        let title = normalize_title(&title)?;
+        require!(title.len() <= TitleReservation::MAX_TITLE_LEN, AdminError::InvalidTitle);
+        let reservation = &mut ctx.accounts.title_reservation;
+        reservation.title = title.clone();
+        reservation.reserved_for = reserved_for;
+        reservation.premium_fee_per_year = premium_fee_per_year;
+        reservation.bump = ctx.bumps.title_reservation;
+
+        emit!(TitleReserved {
+            title,
+            reserved_for,
+            premium_fee_per_year,
+            updated_by: ctx.accounts.admin_operator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    #[access_control(ctx.accounts.authorize())]
+    pub fn release_title(ctx: Context<ReleaseTitle>, title: String) -> Result<()> {
+        emit!(TitleReleased {
+            title,
+            updated_by: ctx.accounts.admin_operator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Emergency switches read by minter, market and vault. `global` halts every
+    /// state-changing instruction; the other flags halt a single area.
+    #[access_control(ctx.accounts.authorize())]
+    pub fn set_pause(
+        ctx: Context<SetPause>,
+        global: bool,
+        minting: bool,
+        transfers: bool,
+        market: bool,
+        vault_withdrawals: bool,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.pause_registry;
+        registry.global = global;
+        registry.minting = minting;
+        registry.transfers = transfers;
+        registry.market = market;
+        registry.vault_withdrawals = vault_withdrawals;
+        registry.bump = ctx.bumps.pause_registry;
+
+        emit!(PauseUpdated {
+            global,
+            minting,
+            transfers,
+            market,
+            vault_withdrawals,
+            updated_by: ctx.accounts.admin_operator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
 }
 
 #[account]
@@ -47,35 +503,302 @@ pub struct AdminXFT {
     pub marketplace_fee_dexsta: u64,
     pub dexsta_address: Pubkey,
     pub bump: u8,
+    pub fee_change_delay: i64,
+    pub version: u8,
+    pub payout_recipients: Vec<PayoutRecipient>,
 }
 
 impl AdminXFT {
+    pub const MAX_PAYOUT_RECIPIENTS: usize = 8;
+    pub const LEN: usize = 8 + 8 + 32 + 8 + 8 + 8 + 32 + 1 + 8 + 1 + (4 + PayoutRecipient::LEN * Self::MAX_PAYOUT_RECIPIENTS);
+    /// Bump when appending fields, and add their defaults to migrate_admin
+    pub const VERSION: u8 = 2;
+    pub const DEFAULT_FEE_CHANGE_DELAY: i64 = 2 * 86400;
+}
+
+/// Share of swept platform fees, weights across all recipients add up to 10000
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PayoutRecipient {
+    pub address: Pubkey,
+    pub weight_bps: u64,
+}
+
+impl PayoutRecipient {
+    pub const LEN: usize = 32 + 8;
+}
+
+/// Mint price table for a single label type, seeds = [b"mint_fee", label_type]
+#[account]
+pub struct MintFeeSchedule {
+    pub label_type: u64,
+    pub fee_per_year: u64,
+    pub title_length_fees: Vec<TitleLengthFee>,
+    pub year_discounts: Vec<YearDiscount>,
+    pub bump: u8,
+}
+
+impl MintFeeSchedule {
+    pub const MAX_TIERS: usize = 8;
+    pub const LEN: usize = 8 + 8 + 8 + (4 + 16 * Self::MAX_TIERS) + (4 + 16 * Self::MAX_TIERS) + 1;
+
+    /// Loads a schedule PDA whose address is already checked, None if it was never created
+    pub fn load(account: &AccountInfo) -> Result<Option<Self>> {
+        if account.data_is_empty() {
+            return Ok(None);
+        }
+        require!(*account.owner == crate::ID, AdminError::InvalidFeeSchedule);
+        let data = account.try_borrow_data()?;
+        let schedule = MintFeeSchedule::try_deserialize(&mut &data[..])?;
+        Ok(Some(schedule))
+    }
+
+    pub fn fee_per_year_for_title(&self, title_len: u64) -> u64 {
+        self.title_length_fees
+            .iter()
+            .filter(|tier| title_len <= tier.max_title_len)
+            .min_by_key(|tier| tier.max_title_len)
+            .map(|tier| tier.fee_per_year)
+            .unwrap_or(self.fee_per_year)
+    }
+
+    pub fn discount_bps(&self, years: u64) -> u64 {
+        self.year_discounts
+            .iter()
+            .filter(|tier| years >= tier.min_years)
+            .max_by_key(|tier| tier.min_years)
+            .map(|tier| tier.discount_bps)
+            .unwrap_or(0)
+    }
+
+    pub fn total_fee(&self, title_len: u64, years: u64) -> u64 {
+        let gross = self.fee_per_year_for_title(title_len).saturating_mul(years) as u128;
+        let discount = gross * self.discount_bps(years) as u128 / 10000;
+        (gross - discount) as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct TitleLengthFee {
+    pub max_title_len: u64,
+    pub fee_per_year: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct YearDiscount {
+    pub min_years: u64,
+    pub discount_bps: u64,
+}
+
+/// Accepted SPL payment mint, seeds = [b"payment_mint", mint]
+#[account]
+pub struct PaymentMint {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub marketplace_fee_bps: u64,
+    pub mint_fee_rate: u64,
+    pub enabled: bool,
+    pub bump: u8,
+}
+
+impl PaymentMint {
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 1 + 1;
+
+    /// Converts a lamport-denominated mint fee into this mint's base units
+    pub fn convert_mint_fee(&self, lamports: u64) -> u64 {
+        (lamports as u128 * self.mint_fee_rate as u128 / 1_000_000_000) as u64
+    }
+}
+
+/// Protocol fee treasury for one payment mint (Pubkey::default() for SOL)
+#[account]
+pub struct Treasury {
+    pub mint: Pubkey,
+    pub total_collected: u64,
+    pub total_swept: u64,
+    pub bump: u8,
+}
+
+impl Treasury {
     pub const LEN: usize = 8 + 32 + 8 + 8 + 1;
 }
 
+/// Blocked wallet, seeds = [b"blocklist", address]
+#[account]
+pub struct BlocklistEntry {
+    pub address: Pubkey,
+    pub added_by: Pubkey,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+impl BlocklistEntry {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+/// Reserved or premium label title, seeds = [b"title_reservation", xft_types::title_seed(title)]
+#[account]
+pub struct TitleReservation {
+    pub title: String,
+    pub reserved_for: Pubkey,
+    pub premium_fee_per_year: u64,
+    pub bump: u8,
+}
+
+impl TitleReservation {
+    pub const MAX_TITLE_LEN: usize = 32;
+    pub const LEN: usize = 8 + (4 + Self::MAX_TITLE_LEN) + 32 + 8 + 1;
+}
+
+/// Emergency pause flags, seeds = [b"pause_registry"]
+#[account]
+pub struct PauseRegistry {
+    pub global: bool,
+    pub minting: bool,
+    pub transfers: bool,
+    pub market: bool,
+    pub vault_withdrawals: bool,
+    pub bump: u8,
+}
+
+impl PauseRegistry {
+    pub const LEN: usize = 8 + 1 + 1 + 1 + 1 + 1 + 1;
+}
+
+/// Pending fee/payout change, applied by `execute_fee_change` once `eta` has passed
+#[account]
+pub struct FeeProposal {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub mint_fee_per_year: u64,
+    pub marketplace_fee_sol: u64,
+    pub marketplace_fee_dexsta: u64,
+    pub payout_address: Pubkey,
+    pub fee_change_delay: i64,
+    pub eta: i64,
+    pub bump: u8,
+    pub payout_recipients: Vec<PayoutRecipient>,
+}
+
+impl FeeProposal {
+    pub const LEN: usize = 8 + 8 + 32 + 8 + 8 + 8 + 32 + 8 + 8 + 1 + (4 + PayoutRecipient::LEN * AdminXFT::MAX_PAYOUT_RECIPIENTS);
+}
+
 #[derive(Accounts)]
 pub struct Initialize<'info> {
-    #[account(init, payer = payer, space = 8 + 8 + 32 + 8 + 8 + 1, seeds = [b"admin_xft"], bump)]
+    #[account(init, payer = payer, space = AdminXFT::LEN, seeds = [b"admin_xft"], bump)]
     pub admin: Account<'info, AdminXFT>,
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+/// Adds `authorize` to accounts structs of instructions restricted to super operators of the
+/// platform XFT, run through `#[access_control(ctx.accounts.authorize())]`. The accounts stay
+/// flat because `declare_program!` in the minter and vault cannot import a composite accounts
+/// struct shared by several instructions.
+macro_rules! super_operator_accounts {
+    ($($accounts:ident),* $(,)?) => {
+        $(
+            impl $accounts<'_> {
+                pub fn authorize(&self) -> Result<()> {
+                    require_super_operator(
+                        &self.admin_operator.key(),
+                        &self.label_account,
+                        &self.operator_account,
+                        self.admin.platform_xft_in,
+                    )
+                }
+            }
+        )*
+    };
+}
+
+super_operator_accounts!(
+    ProposeFeeChange,
+    CancelFeeChange,
+    SetMintFeeSchedule,
+    SetPaymentMint,
+    AddToBlocklist,
+    RemoveFromBlocklist,
+    ReserveTitle,
+    ReleaseTitle,
+    SetPause,
+);
+
 #[derive(Accounts)]
-pub struct SetFees<'info> {
-    #[account(mut, seeds = [b"admin_xft"], bump = admin.bump)]
+pub struct MigrateAdmin<'info> {
+    /// CHECK: May be shorter than AdminXFT::LEN, so it is read manually after the realloc
+    #[account(mut, seeds = [b"admin_xft"], bump)]
+    pub admin: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub admin_operator: Signer<'info>,
+    /// CHECK: Platform label XftAccount, owner and xft_id are checked in is_super_operator
+    pub label_account: UncheckedAccount<'info>,
+    /// CHECK: Operator PDA for (admin_operator, platform_xft_in), checked in is_super_operator
+    pub operator_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ProposeFeeChange<'info> {
+    #[account(seeds = [b"admin_xft"], bump = admin.bump)]
     pub admin: Account<'info, AdminXFT>,
-    /// CHECK: Must be checked in handler
-    pub admin_operator: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = admin_operator,
+        space = FeeProposal::LEN,
+        seeds = [b"fee_proposal", proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub fee_proposal: Account<'info, FeeProposal>,
+    #[account(mut)]
+    pub admin_operator: Signer<'info>,
+    /// CHECK: Platform label XftAccount, owner and xft_id are checked in is_super_operator
+    pub label_account: UncheckedAccount<'info>,
+    /// CHECK: Operator PDA for (admin_operator, platform_xft_in), checked in is_super_operator
+    pub operator_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SetPayoutAddress<'info> {
+#[instruction(proposal_id: u64)]
+pub struct ExecuteFeeChange<'info> {
     #[account(mut, seeds = [b"admin_xft"], bump = admin.bump)]
     pub admin: Account<'info, AdminXFT>,
-    /// CHECK: Must be checked in handler
-    pub admin_operator: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"fee_proposal", proposal_id.to_le_bytes().as_ref()],
+        bump = fee_proposal.bump,
+        close = proposer
+    )]
+    pub fee_proposal: Account<'info, FeeProposal>,
+    /// CHECK: Receives the proposal rent, must match fee_proposal.proposer
+    #[account(mut, address = fee_proposal.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CancelFeeChange<'info> {
+    #[account(seeds = [b"admin_xft"], bump = admin.bump)]
+    pub admin: Account<'info, AdminXFT>,
+    #[account(
+        mut,
+        seeds = [b"fee_proposal", proposal_id.to_le_bytes().as_ref()],
+        bump = fee_proposal.bump,
+        close = proposer
+    )]
+    pub fee_proposal: Account<'info, FeeProposal>,
+    /// CHECK: Receives the proposal rent, must match fee_proposal.proposer
+    #[account(mut, address = fee_proposal.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+    pub admin_operator: Signer<'info>,
+    /// CHECK: Platform label XftAccount, owner and xft_id are checked in is_super_operator
+    pub label_account: UncheckedAccount<'info>,
+    /// CHECK: Operator PDA for (admin_operator, platform_xft_in), checked in is_super_operator
+    pub operator_account: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -84,13 +807,520 @@ pub struct GetFees<'info> {
     pub admin: Account<'info, AdminXFT>,
 }
 
+#[derive(Accounts)]
+#[instruction(label_type: u64)]
+pub struct SetMintFeeSchedule<'info> {
+    #[account(seeds = [b"admin_xft"], bump = admin.bump)]
+    pub admin: Account<'info, AdminXFT>,
+    #[account(
+        init_if_needed,
+        payer = admin_operator,
+        space = MintFeeSchedule::LEN,
+        seeds = [b"mint_fee", label_type.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub fee_schedule: Account<'info, MintFeeSchedule>,
+    #[account(mut)]
+    pub admin_operator: Signer<'info>,
+    /// CHECK: Platform label XftAccount, owner and xft_id are checked in is_super_operator
+    pub label_account: UncheckedAccount<'info>,
+    /// CHECK: Operator PDA for (admin_operator, platform_xft_in), checked in is_super_operator
+    pub operator_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(label_type: u64)]
+pub struct GetMintFee<'info> {
+    #[account(seeds = [b"admin_xft"], bump = admin.bump)]
+    pub admin: Account<'info, AdminXFT>,
+    /// CHECK: MintFeeSchedule PDA for label_type, empty when the type has no schedule; loaded in MintFeeSchedule::load
+    #[account(seeds = [b"mint_fee", label_type.to_le_bytes().as_ref()], bump)]
+    pub fee_schedule: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(init, payer = payer, space = Treasury::LEN, seeds = [b"treasury", Pubkey::default().as_ref()], bump)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeSplTreasury<'info> {
+    pub mint: Account<'info, Mint>,
+    #[account(init, payer = payer, space = Treasury::LEN, seeds = [b"treasury", mint.key().as_ref()], bump)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"treasury_vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = treasury
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositFee<'info> {
+    #[account(mut, seeds = [b"treasury", treasury.mint.as_ref()], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, seeds = [b"treasury_vault", treasury.mint.as_ref()], bump)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub payer_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepFees<'info> {
+    #[account(seeds = [b"admin_xft"], bump = admin.bump)]
+    pub admin: Account<'info, AdminXFT>,
+    #[account(mut, seeds = [b"treasury", treasury.mint.as_ref()], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, seeds = [b"treasury_vault", treasury.mint.as_ref()], bump)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaymentMint<'info> {
+    #[account(seeds = [b"admin_xft"], bump = admin.bump)]
+    pub admin: Account<'info, AdminXFT>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = admin_operator,
+        space = PaymentMint::LEN,
+        seeds = [b"payment_mint", mint.key().as_ref()],
+        bump
+    )]
+    pub payment_mint: Account<'info, PaymentMint>,
+    #[account(mut)]
+    pub admin_operator: Signer<'info>,
+    /// CHECK: Platform label XftAccount, owner and xft_id are checked in is_super_operator
+    pub label_account: UncheckedAccount<'info>,
+    /// CHECK: Operator PDA for (admin_operator, platform_xft_in), checked in is_super_operator
+    pub operator_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct AddToBlocklist<'info> {
+    #[account(seeds = [b"admin_xft"], bump = admin.bump)]
+    pub admin: Account<'info, AdminXFT>,
+    #[account(
+        init,
+        payer = admin_operator,
+        space = BlocklistEntry::LEN,
+        seeds = [b"blocklist", address.as_ref()],
+        bump
+    )]
+    pub blocklist_entry: Account<'info, BlocklistEntry>,
+    #[account(mut)]
+    pub admin_operator: Signer<'info>,
+    /// CHECK: Platform label XftAccount, owner and xft_id are checked in is_super_operator
+    pub label_account: UncheckedAccount<'info>,
+    /// CHECK: Operator PDA for (admin_operator, platform_xft_in), checked in is_super_operator
+    pub operator_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct RemoveFromBlocklist<'info> {
+    #[account(seeds = [b"admin_xft"], bump = admin.bump)]
+    pub admin: Account<'info, AdminXFT>,
+    #[account(
+        mut,
+        seeds = [b"blocklist", address.as_ref()],
+        bump = blocklist_entry.bump,
+        close = admin_operator
+    )]
+    pub blocklist_entry: Account<'info, BlocklistEntry>,
+    #[account(mut)]
+    pub admin_operator: Signer<'info>,
+    /// CHECK: Platform label XftAccount, owner and xft_id are checked in is_super_operator
+    pub label_account: UncheckedAccount<'info>,
+    /// CHECK: Operator PDA for (admin_operator, platform_xft_in), checked in is_super_operator
+    pub operator_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(title: String)]
+pub struct ReserveTitle<'info> {
+    #[account(seeds = [b"admin_xft"], bump = admin.bump)]
+    pub admin: Account<'info, AdminXFT>,
+    #[account(
+        init_if_needed,
+        payer = admin_operator,
+        space = TitleReservation::LEN,
+        seeds = [b"title_reservation", &title_seed(&title)[..]],
+        bump
+    )]
+    pub title_reservation: Account<'info, TitleReservation>,
+    #[account(mut)]
+    pub admin_operator: Signer<'info>,
+    /// CHECK: Platform label XftAccount, owner and xft_id are checked in is_super_operator
+    pub label_account: UncheckedAccount<'info>,
+    /// CHECK: Operator PDA for (admin_operator, platform_xft_in), checked in is_super_operator
+    pub operator_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(title: String)]
+pub struct ReleaseTitle<'info> {
+    #[account(seeds = [b"admin_xft"], bump = admin.bump)]
+    pub admin: Account<'info, AdminXFT>,
+    #[account(
+        mut,
+        seeds = [b"title_reservation", &title_seed(&title)[..]],
+        bump = title_reservation.bump,
+        close = admin_operator
+    )]
+    pub title_reservation: Account<'info, TitleReservation>,
+    #[account(mut)]
+    pub admin_operator: Signer<'info>,
+    /// CHECK: Platform label XftAccount, owner and xft_id are checked in is_super_operator
+    pub label_account: UncheckedAccount<'info>,
+    /// CHECK: Operator PDA for (admin_operator, platform_xft_in), checked in is_super_operator
+    pub operator_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(seeds = [b"admin_xft"], bump = admin.bump)]
+    pub admin: Account<'info, AdminXFT>,
+    #[account(
+        init_if_needed,
+        payer = admin_operator,
+        space = PauseRegistry::LEN,
+        seeds = [b"pause_registry"],
+        bump
+    )]
+    pub pause_registry: Account<'info, PauseRegistry>,
+    #[account(mut)]
+    pub admin_operator: Signer<'info>,
+    /// CHECK: Platform label XftAccount, owner and xft_id are checked in is_super_operator
+    pub label_account: UncheckedAccount<'info>,
+    /// CHECK: Operator PDA for (admin_operator, platform_xft_in), checked in is_super_operator
+    pub operator_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 #[error_code]
 pub enum AdminError {
-    #[msg("Unauthorized: Only super operators linked to the platform XFT can update fees or payout address")] 
+    #[msg("Unauthorized: Only super operators linked to the platform XFT can run admin instructions")]
     Unauthorized,
+    #[msg("Fee change delay must be a non-negative number of seconds")]
+    InvalidDelay,
+    #[msg("Fee change proposal cannot be executed before its eta")]
+    ProposalNotReady,
+    #[msg("Invalid mint fee schedule")]
+    InvalidFeeSchedule,
+    #[msg("Admin account is not owned by this program")]
+    InvalidAdminAccount,
+    #[msg("Admin account is already at the current version")]
+    AlreadyMigrated,
+    #[msg("Decimals do not match the payment mint")]
+    DecimalsMismatch,
+    #[msg("Fee in basis points must not exceed 10000")]
+    InvalidFeeBps,
+    #[msg("Treasury accounts do not match the treasury mint or payout address")]
+    InvalidTreasury,
+    #[msg("Payout recipients must be non-empty, unique and weigh 10000 in total")]
+    InvalidPayoutRecipients,
+    #[msg("Title must be non-empty, without whitespace and at most 32 bytes")]
+    InvalidTitle,
+}
+
+#[event]
+pub struct AdminMigrated {
+    pub from_version: u8,
+    pub to_version: u8,
+    pub migrated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeChangeProposed {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub mint_fee_per_year: u64,
+    pub marketplace_fee_sol: u64,
+    pub marketplace_fee_dexsta: u64,
+    pub payout_address: Pubkey,
+    pub payout_recipients: Vec<PayoutRecipient>,
+    pub fee_change_delay: i64,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeChangeExecuted {
+    pub proposal_id: u64,
+    pub executor: Pubkey,
+    pub mint_fee_per_year: u64,
+    pub marketplace_fee_sol: u64,
+    pub marketplace_fee_dexsta: u64,
+    pub payout_address: Pubkey,
+    pub payout_recipients: Vec<PayoutRecipient>,
+    pub fee_change_delay: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintFeeScheduleSet {
+    pub label_type: u64,
+    pub fee_per_year: u64,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeDeposited {
+    pub mint: Pubkey,
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub total_collected: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeesSwept {
+    pub mint: Pubkey,
+    pub recipients: Vec<Pubkey>,
+    pub amounts: Vec<u64>,
+    pub amount: u64,
+    pub total_swept: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PaymentMintUpdated {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub marketplace_fee_bps: u64,
+    pub mint_fee_rate: u64,
+    pub enabled: bool,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddressBlocked {
+    pub address: Pubkey,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddressUnblocked {
+    pub address: Pubkey,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TitleReserved {
+    pub title: String,
+    pub reserved_for: Pubkey,
+    pub premium_fee_per_year: u64,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TitleReleased {
+    pub title: String,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub global: bool,
+    pub minting: bool,
+    pub transfers: bool,
+    pub market: bool,
+    pub vault_withdrawals: bool,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeChangeCancelled {
+    pub proposal_id: u64,
+    pub cancelled_by: Pubkey,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+/// Returns true if `admin_operator` owns the platform label (`platform_xft_in`) and the
+/// registration has not expired, or holds an unexpired role-1 operator entry for it (the
+/// xft-operator PDA [b"operator", admin_operator, platform_xft_in]).
+pub fn is_super_operator(
+    admin_operator: &Pubkey,
+    label_account: &AccountInfo,
+    operator_account: &AccountInfo,
+    platform_xft_in: u64,
+) -> Result<bool> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let minter_program_id = Pubkey::from_str(MINTER_PROGRAM_ID).unwrap();
+
+    // Label owner path: addresses[1] is the owner, settings[7] the registration expiry
+    if *label_account.owner == minter_program_id && label_account.data_len() > 8 {
+        let data = label_account.try_borrow_data()?;
+        if let Ok(label) = XftAccount::deserialize(&mut &data[8..]) {
+            let owner = label.addresses.get(1).copied().unwrap_or_default();
+            let expire = label.settings.get(7).copied().unwrap_or(0);
+            let is_label_pda = xft_account_address(&minter_program_id, label.xft_id, label.bump) == Some(label_account.key());
+            if is_label_pda && label.xft_id == platform_xft_in && owner == *admin_operator && expire > now {
+                return Ok(true);
+            }
+        }
+    }
+
+    // Operator path: role 1 is the super operator
+    Ok(active_operator_role(operator_account, admin_operator, platform_xft_in, now) == Some(1))
+}
+
+/// Fails with `Unauthorized` unless `is_super_operator` holds
+pub fn require_super_operator(
+    admin_operator: &Pubkey,
+    label_account: &AccountInfo,
+    operator_account: &AccountInfo,
+    platform_xft_in: u64,
+) -> Result<()> {
+    require!(
+        is_super_operator(admin_operator, label_account, operator_account, platform_xft_in)?,
+        AdminError::Unauthorized
+    );
+    Ok(())
+}
+
+pub fn validate_payout_recipients(recipients: &[PayoutRecipient]) -> Result<()> {
+    require!(
+        !recipients.is_empty() && recipients.len() <= AdminXFT::MAX_PAYOUT_RECIPIENTS,
+        AdminError::InvalidPayoutRecipients
+    );
+    let mut total = 0u64;
+    for (i, recipient) in recipients.iter().enumerate() {
+        require!(recipient.address != Pubkey::default() && recipient.weight_bps > 0, AdminError::InvalidPayoutRecipients);
+        require!(
+            recipients[..i].iter().all(|other| other.address != recipient.address),
+            AdminError::InvalidPayoutRecipients
+        );
+        total = total.saturating_add(recipient.weight_bps);
+    }
+    require!(total == 10000, AdminError::InvalidPayoutRecipients);
+    Ok(())
+}
+
+/// Splits `amount` by recipient weight; the primary (first) recipient also takes the rounding dust
+pub fn split_by_weight(amount: u64, recipients: &[PayoutRecipient]) -> Vec<u64> {
+    let mut shares: Vec<u64> = recipients
+        .iter()
+        .map(|r| (amount as u128 * r.weight_bps as u128 / 10000) as u64)
+        .collect();
+    let distributed: u64 = shares.iter().sum();
+    if let Some(primary) = shares.first_mut() {
+        *primary += amount - distributed;
+    }
+    shares
 }
 
-fn is_super_operator(_admin_operator: &AccountInfo, _platform_xft_in: u64) -> bool {
-    // TODO: Implement CPI to operator program to check if admin_operator is a super operator for platform_xft_in
-    true // placeholder
-}
\ No newline at end of file
+pub const MINTER_PROGRAM_ID: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+
+// Local mirror of minter::XftAccount for reading the platform label
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct XftAccount {
+    pub xft_id: u64,
+    pub settings: Vec<u64>,
+    pub addresses: Vec<Pubkey>,
+    pub ipfs: String,
+    pub bump: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(title_length_fees: &[(u64, u64)], year_discounts: &[(u64, u64)]) -> MintFeeSchedule {
+        MintFeeSchedule {
+            label_type: 1,
+            fee_per_year: 1_000,
+            title_length_fees: title_length_fees
+                .iter()
+                .map(|&(max_title_len, fee_per_year)| TitleLengthFee { max_title_len, fee_per_year })
+                .collect(),
+            year_discounts: year_discounts
+                .iter()
+                .map(|&(min_years, discount_bps)| YearDiscount { min_years, discount_bps })
+                .collect(),
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn fee_per_year_picks_smallest_fitting_tier() {
+        // Tiers are deliberately out of order
+        let s = schedule(&[(5, 20_000), (3, 50_000), (4, 30_000)], &[]);
+        assert_eq!(s.fee_per_year_for_title(1), 50_000);
+        assert_eq!(s.fee_per_year_for_title(3), 50_000);
+        assert_eq!(s.fee_per_year_for_title(4), 30_000);
+        assert_eq!(s.fee_per_year_for_title(5), 20_000);
+        // Longer than every tier falls back to the base price
+        assert_eq!(s.fee_per_year_for_title(6), 1_000);
+        assert_eq!(schedule(&[], &[]).fee_per_year_for_title(1), 1_000);
+    }
+
+    #[test]
+    fn discount_picks_largest_reached_tier() {
+        let s = schedule(&[], &[(5, 2_000), (2, 500), (3, 1_000)]);
+        assert_eq!(s.discount_bps(1), 0);
+        assert_eq!(s.discount_bps(2), 500);
+        assert_eq!(s.discount_bps(3), 1_000);
+        assert_eq!(s.discount_bps(4), 1_000);
+        assert_eq!(s.discount_bps(10), 2_000);
+    }
+
+    #[test]
+    fn total_fee_prices_short_titles_and_discounts() {
+        let s = schedule(&[(3, 50_000)], &[(2, 1_000)]);
+        assert_eq!(s.total_fee(3, 1), 50_000);
+        assert_eq!(s.total_fee(3, 2), 90_000);
+        assert_eq!(s.total_fee(8, 1), 1_000);
+        assert_eq!(s.total_fee(8, 4), 3_600);
+        assert_eq!(s.total_fee(8, 0), 0);
+    }
+
+    #[test]
+    fn total_fee_full_discount_is_free() {
+        let s = schedule(&[(3, 50_000)], &[(1, 10_000)]);
+        assert_eq!(s.total_fee(3, 5), 0);
+        assert_eq!(s.total_fee(8, 1), 0);
+        // The discount rounds down, so anything under 100% still charges something
+        let s = schedule(&[], &[(1, 9_999)]);
+        assert_eq!(s.total_fee(8, 1), 1);
+    }
+
+    #[test]
+    fn total_fee_saturates_instead_of_overflowing() {
+        let s = schedule(&[(3, u64::MAX)], &[(2, 5_000)]);
+        assert_eq!(s.total_fee(3, 1), u64::MAX);
+        assert_eq!(s.total_fee(3, 3), u64::MAX / 2 + 1);
+    }
+}
@@ -1,11 +1,13 @@
 use anchor_lang::prelude::*;
 declare_program!(minter);
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenInterface};
 use borsh::{BorshDeserialize, BorshSerialize};
 
 // Correct Anchor CPI imports for admin_xft
 use admin_xft::cpi::accounts::GetFees;
 use admin_xft::cpi::get_fees;
+use xft_types::{transfer_checked_with_hook, xft_account_address, XftSettings};
 
 declare_id!("JDmExoWsKJe7eMEcxaNKgBe1dgHXe5ns3wGqxgc7kAez");
 
@@ -28,23 +30,38 @@ pub mod xft_market {
     //settings[11] = auction_max_price
     //settings[12] = auction_buy_now_price
     //settings[13] = marketplace_fee_percentage
-    //settings[14] = payment token 1 = sol, 2 = dexsta
+    //settings[14] = payment token 1 = sol, 2 = spl (any enabled xft-admin payment mint)
+    //settings[15] = edition number for numbered edition listings, 0 = no edition
 
     //addresses[0] = seller
     //addresses[1] = label_vault
     //addresses[2] = operator
     //addresses[3] = seller_payout_address
     //addresses[4] = platform_payout_address
-    
-    pub fn sell(
-        ctx: Context<Sell>,
+    //addresses[5] = payment mint (spl listings only)
+    //addresses[6] = listed mint, the edition's own mint for edition listings, held in the listing escrow
+
+    pub fn sell<'info>(
+        ctx: Context<'_, '_, '_, 'info, Sell<'info>>,
         xft_id: u64,
         settings: Vec<u64>,
         seller_payout_address: Pubkey,
     ) -> Result<()> {
+        require_market_not_paused(&ctx.accounts.pause_registry)?;
+        require_not_blocked(&ctx.accounts.seller_blocklist_entry, &ctx.accounts.seller.key())?;
         let mut settings = settings;
         // Validate inputs
         require!(settings[3] > 0, MarketError::InvalidQuantity);
+        // Edition listings sell exactly one numbered edition, the token of its own mint
+        let edition = settings.get(15).copied().unwrap_or(0);
+        let listed_mint = if edition > 0 {
+            require!(settings[3] == 1, MarketError::InvalidQuantity);
+            let edition_account = ctx.accounts.edition.as_ref().ok_or(MarketError::InvalidEdition)?;
+            Edition::load(edition_account, xft_id, edition)?.mint
+        } else {
+            Pubkey::find_program_address(&[b"xft_mint", xft_id.to_le_bytes().as_ref()], &minter::ID).0
+        };
+        require!(ctx.accounts.xft_mint.key() == listed_mint, MarketError::InvalidListedMint);
         let mut allowed = false;
         let mut addresses = Vec::new();
         addresses.push(ctx.accounts.seller.key());
@@ -52,6 +69,16 @@ pub mod xft_market {
         addresses.push(Pubkey::default()); // dead address to be updated later
         addresses.push(seller_payout_address); // dead address to be updated later
         addresses.push(Pubkey::default()); // dead address to be updated later
+        let payment_type = settings.get(14).copied().unwrap_or(0);
+        require!(payment_type == 1 || payment_type == 2, MarketError::PaymentMintNotAccepted);
+        if payment_type == 2 {
+            let payment_mint = ctx.accounts.payment_mint.as_ref().ok_or(MarketError::PaymentMintNotAccepted)?;
+            require!(payment_mint.enabled, MarketError::PaymentMintNotAccepted);
+            addresses.push(payment_mint.mint);
+        } else {
+            addresses.push(Pubkey::default());
+        }
+        addresses.push(listed_mint);
         
         if settings[1] > 0 && settings[1] == 0 {
             // Item is being sold under a label, check if caller is label owner
@@ -85,7 +112,7 @@ pub mod xft_market {
         if settings[0] > 0 && settings[1] > 0 {
             ///caller is using a market license to sell under this label
             // Check if cmarket license is valid
-            let (is_license_valid, returned_label_id) = is_market_license(&ctx.accounts.xft_account, &ctx.accounts.parent_xft_account)?;
+            let (is_license_valid, returned_label_id) = is_market_license(&ctx.accounts.xft_account, &ctx.accounts.parent_xft_account, xft_id)?;
             allowed = is_license_valid && returned_label_id == settings[0];
             if allowed {
                 // Set addresses[1] as caller
@@ -113,19 +140,29 @@ pub mod xft_market {
         if settings[0] > 0 {
 
             // Add child to parent
-            add_child_to_parent_xft(&ctx.accounts.parent_xft_account, xft_id)?;
+            add_child_to_parent_xft(
+                &ctx.accounts.xft_minter_program,
+                &ctx.accounts.escrow_authority,
+                ctx.bumps.escrow_authority,
+                &ctx.accounts.parent_xft_account,
+                settings[0],
+                xft_id,
+            )?;
         }
 
-        // Transfer XFT from seller to escrow (listing account)
+        // Transfer XFT from seller to the listing escrow, remaining accounts carry the
+        // transfer hook accounts of restricted mints
         let transfer_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
+            token_interface::TransferChecked {
                 from: ctx.accounts.seller_xft_token_account.to_account_info(),
-                to: ctx.accounts.listing_account.to_account_info(),
+                mint: ctx.accounts.xft_mint.to_account_info(),
+                to: ctx.accounts.listing_escrow.to_account_info(),
                 authority: ctx.accounts.seller.to_account_info(),
             },
-        );
-        token::transfer(transfer_ctx, settings[3])?; // Transfer quantity amount
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        transfer_checked_with_hook(transfer_ctx, settings[3], ctx.accounts.xft_mint.decimals)?;
 
         // Save the listing
         let listing_account_info = &ctx.accounts.listing_account;
@@ -146,10 +183,11 @@ pub mod xft_market {
         Ok(())
     }
 
-    pub fn cancel_sell(
-        ctx: Context<CancelSell>,
+    pub fn cancel_sell<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelSell<'info>>,
         xft_id: u64,
     ) -> Result<()> {
+        require_market_not_paused(&ctx.accounts.pause_registry)?;
         // Fetch the listing
         let listing_account_info = &ctx.accounts.listing_account;
         let mut data = listing_account_info.try_borrow_mut_data()?;
@@ -162,11 +200,32 @@ pub mod xft_market {
             MarketError::NotAuthorized
         );
 
-        // Mark as inactive
+        // Mark as inactive and return the unsold quantity from the escrow
         listing.is_active = false;
+        require!(listing.addresses.get(6) == Some(&ctx.accounts.xft_mint.key()), MarketError::InvalidListedMint);
+        release_from_escrow(
+            &ctx.accounts.listing_escrow,
+            &ctx.accounts.xft_mint,
+            &ctx.accounts.seller_xft_token_account,
+            &ctx.accounts.escrow_authority,
+            ctx.bumps.escrow_authority,
+            &ctx.accounts.token_program,
+            ctx.remaining_accounts,
+            listing.quantity,
+        )?;
+        listing.quantity = 0;
         
         // Set settings[5] = 0 and remove xft_id from parent settings[0]
-        remove_child_from_parent(&ctx.accounts.parent_xft_account, xft_id)?;
+        if listing.settings[0] > 0 {
+            remove_child_from_parent(
+                &ctx.accounts.xft_minter_program,
+                &ctx.accounts.escrow_authority,
+                ctx.bumps.escrow_authority,
+                &ctx.accounts.parent_xft_account,
+                listing.settings[0],
+                xft_id,
+            )?;
+        }
         
         // Update the listing
         listing_account.listing = listing;
@@ -186,6 +245,7 @@ pub mod xft_market {
         new_price: u64,
         new_price_type: u64,
     ) -> Result<()> {
+        require_market_not_paused(&ctx.accounts.pause_registry)?;
         // Validate inputs
         require!(new_price > 0, MarketError::InvalidPrice);
         // Fetch the listing
@@ -213,11 +273,19 @@ pub mod xft_market {
         Ok(())
     }
 
-    pub fn buy(
-        ctx: Context<Buy>,
+    /// The first `payment_hook_accounts` remaining accounts are the transfer hook accounts of
+    /// the payment mint, the rest those of the listed mint.
+    pub fn buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, Buy<'info>>,
         xft_id: u64,
         quantity: u64,
+        payment_hook_accounts: u8,
     ) -> Result<()> {
+        require!(ctx.remaining_accounts.len() >= payment_hook_accounts as usize, MarketError::PaymentMintNotAccepted);
+        let (payment_hook_accounts, xft_hook_accounts) = ctx.remaining_accounts.split_at(payment_hook_accounts as usize);
+        require_market_not_paused(&ctx.accounts.pause_registry)?;
+        require_not_blocked(&ctx.accounts.buyer_blocklist_entry, &ctx.accounts.buyer.key())?;
+        require_not_blocked(&ctx.accounts.seller_blocklist_entry, &ctx.accounts.seller.key())?;
         // Restore correct CPI context for get_fees
         let cpi_program = ctx.accounts.xft_admin_program.to_account_info();
         let cpi_accounts = GetFees {
@@ -225,13 +293,7 @@ pub mod xft_market {
         };
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         let result = get_fees(cpi_ctx)?;
-        let (_mint_fee_per_year, marketplace_fee_sol, marketplace_fee_dexsta, dexsta_address, payout_address) = result.get();
-
-        // Placeholder values for admin fees and addresses
-        let marketplace_fee_sol = marketplace_fee_sol;
-        let marketplace_fee_dexsta = marketplace_fee_dexsta;
-        let payout_address = payout_address;
-        let dexsta_address = dexsta_address;
+        let (_mint_fee_per_year, marketplace_fee_sol, _marketplace_fee_dexsta, _dexsta_address, _payout_address) = result.get();
 
         // Validate inputs
         require!(quantity > 0, MarketError::InvalidQuantity);
@@ -250,6 +312,28 @@ pub mod xft_market {
         
         // Check if this is a licensed label with fees
         let settings = &listing.settings;
+
+        // SPL payments must use the listing's payment mint, which must still be enabled in xft-admin
+        let mut spl_fee_bps = 0;
+        let mut buyer_payment_account = None;
+        let mut payment_token_mint = None;
+        if settings.get(14).copied().unwrap_or(0) == 2 {
+            let payment_mint = ctx.accounts.payment_mint.as_ref().ok_or(MarketError::PaymentMintNotAccepted)?;
+            require!(
+                payment_mint.enabled && listing.addresses.get(5) == Some(&payment_mint.mint),
+                MarketError::PaymentMintNotAccepted
+            );
+            let buyer_token_account = ctx.accounts.buyer_payment_token_account.as_ref().ok_or(MarketError::PaymentMintNotAccepted)?;
+            require!(
+                buyer_token_account.mint == payment_mint.mint && buyer_token_account.owner == ctx.accounts.buyer.key(),
+                MarketError::PaymentMintNotAccepted
+            );
+            let token_mint = ctx.accounts.payment_token_mint.as_ref().ok_or(MarketError::PaymentMintNotAccepted)?;
+            require!(token_mint.key() == payment_mint.mint, MarketError::PaymentMintNotAccepted);
+            spl_fee_bps = payment_mint.marketplace_fee_bps;
+            buyer_payment_account = Some(buyer_token_account.to_account_info());
+            payment_token_mint = Some(token_mint);
+        }
         
         let mut total_cost = settings[2] * quantity;
         let mut label_fee = 0;
@@ -273,10 +357,11 @@ pub mod xft_market {
                 // Payment is in SPL token - handle SPL token transfer for marketplace fee
                 if let Some(fee_recipient) = listing.addresses.get(1) {
                     if *fee_recipient != Pubkey::default() {
-                        marketplace_fee_amount = (total_cost * marketplace_fee_dexsta) / 10000;
+                        marketplace_fee_amount = (total_cost * spl_fee_bps) / 10000;
                         let payout_account = get_account_info_for_pubkey(&ctx, fee_recipient).expect("payout account not found");
-                        let authority_account = get_account_info_for_pubkey(&ctx, &dexsta_address).expect("authority not found");
-                        pay_spl(&ctx.accounts.buyer.to_account_info(), &payout_account, &authority_account, &ctx.accounts.token_program.to_account_info(), marketplace_fee_amount)?;
+                        let from = buyer_payment_account.clone().ok_or(MarketError::PaymentMintNotAccepted)?;
+                        let mint = payment_token_mint.ok_or(MarketError::PaymentMintNotAccepted)?;
+                        pay_spl(&from, mint, &payout_account, &ctx.accounts.buyer.to_account_info(), &ctx.accounts.token_program.to_account_info(), payment_hook_accounts, marketplace_fee_amount)?;
                     }
                 }
             }
@@ -286,29 +371,31 @@ pub mod xft_market {
         let marketplace_fee = if settings.get(14).copied().unwrap_or(0) == 1 {
             marketplace_fee_sol
         } else {
-            if marketplace_fee_dexsta > 0 {
-                marketplace_fee_dexsta
-            } else {
-                0
-            }
+            spl_fee_bps
         };
         let marketplace_fee_amount = (total_cost * marketplace_fee) / 10000; // Assuming fee is in basis points
         if marketplace_fee_amount > 0 {
-            if settings.get(14).copied().unwrap_or(0) == 1 {
-                let payout_account = get_account_info_for_pubkey(&ctx, &listing.addresses[1]).expect("payout account not found");
-                pay_sol(&ctx.accounts.buyer.to_account_info(), &payout_account, marketplace_fee_amount)?;
-            } else if settings.get(14).copied().unwrap_or(0) == 2 {
-                let payout_account = get_account_info_for_pubkey(&ctx, &listing.addresses[1]).expect("payout account not found");
-                let authority_account = get_account_info_for_pubkey(&ctx, &dexsta_address).expect("authority not found");
-                pay_spl(&ctx.accounts.buyer.to_account_info(), &payout_account, &authority_account, &ctx.accounts.token_program.to_account_info(), marketplace_fee_amount)?;
-            }
-
-            // Set listing.addresses[4] to admin payout address
-            let mut updated_listing = listing.clone();
-            while updated_listing.addresses.len() <= 4 {
-                updated_listing.addresses.push(Pubkey::default());
-            }
-            updated_listing.addresses[4] = payout_address;
+            // Platform fee goes to the xft-admin treasury for the listing's payment mint
+            let payment_type = settings.get(14).copied().unwrap_or(0);
+            let treasury_mint = if payment_type == 2 {
+                listing.addresses.get(5).copied().unwrap_or_default()
+            } else {
+                Pubkey::default()
+            };
+            require!(ctx.accounts.treasury.mint == treasury_mint, MarketError::InvalidTreasury);
+            let spl_payment = payment_type == 2;
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.xft_admin_program.to_account_info(),
+                admin_xft::cpi::accounts::DepositFee {
+                    treasury: ctx.accounts.treasury.to_account_info(),
+                    payer: ctx.accounts.buyer.to_account_info(),
+                    treasury_token_account: ctx.accounts.treasury_token_account.clone().filter(|_| spl_payment),
+                    payer_token_account: buyer_payment_account.clone().filter(|_| spl_payment),
+                    token_program: Some(ctx.accounts.token_program.to_account_info()).filter(|_| spl_payment),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+            );
+            admin_xft::cpi::deposit_fee(cpi_ctx, marketplace_fee_amount)?;
         }
         total_cost += marketplace_fee_amount;
                 
@@ -323,26 +410,41 @@ pub mod xft_market {
             pay_sol(&ctx.accounts.buyer.to_account_info(), &payout_account, total_cost)?;
         } else if settings.get(14).copied().unwrap_or(0) == 2 {
             let payout_account = get_account_info_for_pubkey(&ctx, &payment_recipient).expect("payout account not found");
-            let authority_account = get_account_info_for_pubkey(&ctx, &dexsta_address).expect("authority not found");
-            pay_spl(&ctx.accounts.buyer.to_account_info(), &payout_account, &authority_account, &ctx.accounts.token_program.to_account_info(), total_cost)?;
+            let from = buyer_payment_account.clone().ok_or(MarketError::PaymentMintNotAccepted)?;
+            let mint = payment_token_mint.ok_or(MarketError::PaymentMintNotAccepted)?;
+            pay_spl(&from, mint, &payout_account, &ctx.accounts.buyer.to_account_info(), &ctx.accounts.token_program.to_account_info(), payment_hook_accounts, total_cost)?;
         }
         
-        // Transfer XFT to buyer
-        let xft_program = ctx.accounts.xft_minter_program.to_account_info();
-        let cpi_accounts = minter::cpi::accounts::TransferXft {
-            from: ctx.accounts.seller.clone(),
-            to: ctx.accounts.buyer.to_account_info(),
-            xft_account: ctx.accounts.xft_account.clone(),
-        };
-        let cpi_ctx = CpiContext::new(xft_program, cpi_accounts);
-        minter::cpi::transfer_xft(cpi_ctx)?;
+        // Release the XFT from the listing escrow to the buyer, for edition listings this is
+        // the edition's own mint
+        let edition = settings.get(15).copied().unwrap_or(0);
+        require!(listing.addresses.get(6) == Some(&ctx.accounts.xft_mint.key()), MarketError::InvalidListedMint);
+        release_from_escrow(
+            &ctx.accounts.listing_escrow,
+            &ctx.accounts.xft_mint,
+            &ctx.accounts.buyer_xft_token_account,
+            &ctx.accounts.escrow_authority,
+            ctx.bumps.escrow_authority,
+            &ctx.accounts.xft_token_program,
+            xft_hook_accounts,
+            quantity,
+        )?;
         // Update listing quantity
         let mut updated_listing = listing.clone();
         updated_listing.quantity -= quantity;
         
         if updated_listing.quantity == 0 {
             updated_listing.is_active = false;
-            remove_child_from_parent(&ctx.accounts.parent_xft_account, xft_id)?;
+            if settings[0] > 0 {
+                remove_child_from_parent(
+                    &ctx.accounts.xft_minter_program,
+                    &ctx.accounts.escrow_authority,
+                    ctx.bumps.escrow_authority,
+                    &ctx.accounts.parent_xft_account,
+                    settings[0],
+                    xft_id,
+                )?;
+            }
         }
 
         // Save the updated listing
@@ -356,85 +458,179 @@ pub mod xft_market {
             buyer: ctx.accounts.buyer.key(),
             seller: listing.seller,
             xft_id,
+            edition,
             quantity,
             total_cost,
         });
 
         Ok(())
     }
+
+    /// Opens a declining-price reclaim auction for a lead or profile label whose grace period
+    /// has run out. Anyone can start it and gets the auction rent back when it settles.
+    pub fn start_reclaim_auction(ctx: Context<StartReclaimAuction>, xft_id: u64) -> Result<()> {
+        require_market_not_paused(&ctx.accounts.pause_registry)?;
+        let label = XftAccount::load(&ctx.accounts.label_account, xft_id)?;
+        let settings = label.xft_settings()?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(settings.is_reclaimable(now as u64), MarketError::LabelNotReclaimable);
+        // Prices follow the xft-admin yearly mint fee, the floor is one year of registration
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.xft_admin_program.to_account_info(),
+            GetFees {
+                admin: ctx.accounts.admin_account.clone(),
+            },
+        );
+        let (mint_fee_per_year, _, _, _, _) = get_fees(cpi_ctx)?.get();
+        let auction = &mut ctx.accounts.reclaim_auction;
+        auction.xft_id = xft_id;
+        auction.previous_owner = label.addresses[1];
+        auction.start_price = mint_fee_per_year.saturating_mul(ReclaimAuction::START_PRICE_MULTIPLE);
+        auction.floor_price = mint_fee_per_year;
+        auction.start_time = now;
+        auction.end_time = now + ReclaimAuction::DURATION;
+        auction.creator = ctx.accounts.payer.key();
+        auction.bump = ctx.bumps.reclaim_auction;
+        emit!(ReclaimAuctionStarted {
+            xft_id,
+            previous_owner: auction.previous_owner,
+            start_price: auction.start_price,
+            floor_price: auction.floor_price,
+            end_time: auction.end_time,
+        });
+        Ok(())
+    }
+
+    /// Buys a label in a reclaim auction at the current price. The price goes to the xft-admin
+    /// SOL treasury and pays for the winner's first year of registration.
+    pub fn claim_reclaim_auction(ctx: Context<ClaimReclaimAuction>, xft_id: u64) -> Result<()> {
+        require_market_not_paused(&ctx.accounts.pause_registry)?;
+        require_not_blocked(&ctx.accounts.buyer_blocklist_entry, &ctx.accounts.buyer.key())?;
+        require!(ctx.accounts.treasury.mint == Pubkey::default(), MarketError::InvalidTreasury);
+        let now = Clock::get()?.unix_timestamp;
+        let price = ctx.accounts.reclaim_auction.current_price(now);
+        if price > 0 {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.xft_admin_program.to_account_info(),
+                admin_xft::cpi::accounts::DepositFee {
+                    treasury: ctx.accounts.treasury.to_account_info(),
+                    payer: ctx.accounts.buyer.to_account_info(),
+                    treasury_token_account: None,
+                    payer_token_account: None,
+                    token_program: None,
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+            );
+            admin_xft::cpi::deposit_fee(cpi_ctx, price)?;
+        }
+        // Hand the label and its vault to the buyer, signed by the market reclaim authority
+        let signer_seeds: &[&[&[u8]]] = &[&[b"reclaim_authority", &[ctx.bumps.market_authority]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.xft_minter_program.to_account_info(),
+            minter::cpi::accounts::ReclaimLabel {
+                market_authority: ctx.accounts.market_authority.to_account_info(),
+                label_account: ctx.accounts.label_account.to_account_info(),
+                vault: ctx.accounts.vault.clone(),
+                vault_authority: ctx.accounts.vault_authority.to_account_info(),
+                vault_program: ctx.accounts.vault_program.to_account_info(),
+                previous_claimant: ctx.accounts.previous_claimant.clone(),
+            },
+            signer_seeds,
+        );
+        minter::cpi::reclaim_label(cpi_ctx, xft_id, ctx.accounts.buyer.key(), ReclaimAuction::REGISTRATION_YEARS)?;
+        emit!(ReclaimAuctionSettled {
+            xft_id,
+            winner: ctx.accounts.buyer.key(),
+            previous_owner: ctx.accounts.reclaim_auction.previous_owner,
+            price,
+            timestamp: now,
+        });
+        Ok(())
+    }
 }
 
 // Add this helper function for market license check
-pub fn is_market_license(xft_account_info: &AccountInfo, parent_xft_account_info: &AccountInfo) -> Result<(bool, u64)> {
-    let xft_account = XftAccount::try_from_slice(&xft_account_info.data.borrow())?;
-    let settings = xft_account.settings;
-    // Check if settings[7] > now (expire check)
-    let expire = settings.get(7).copied().unwrap_or(0);
+pub fn is_market_license(xft_account_info: &AccountInfo, parent_xft_account_info: &AccountInfo, xft_id: u64) -> Result<(bool, u64)> {
+    let xft_account = XftAccount::load(xft_account_info, xft_id)?;
+    let settings = xft_account.xft_settings()?;
     let now = Clock::get()?.unix_timestamp as u64;
-    if expire <= now {
+    if settings.is_expired(now) {
         return Ok((false, 0));
     }
-    // Get the parent xft account (settings[0] of xft_id)
-    let parent_xft_id = settings.get(0).copied().unwrap_or(0);
+    // Get the parent label account
+    let parent_xft_id = settings.label_id;
     if parent_xft_id == 0 {
         return Ok((false, 0));
     }
-    let parent_xft_account = XftAccount::try_from_slice(&parent_xft_account_info.data.borrow())?;
-    let parent_settings = parent_xft_account.settings;
-    // Check if settings[7] of parent_xft account > now
-    let parent_expire = parent_settings.get(7).copied().unwrap_or(0);
-    if parent_expire <= now {
+    let parent_xft_account = XftAccount::load(parent_xft_account_info, parent_xft_id)?;
+    if parent_xft_account.xft_settings()?.is_expired(now) {
         return Ok((false, parent_xft_id));
     }
     Ok((true, parent_xft_id))
 }
 
-// Helper function to add a child XFT to a parent XFT's settings array
-pub fn add_child_to_parent_xft(
-    parent_xft_account_info: &AccountInfo,
+// Helper function to add a child XFT to a parent XFT's children list. The parent is owned by
+// xft-minter, which updates it for the market escrow authority
+pub fn add_child_to_parent_xft<'info>(
+    minter_program: &AccountInfo<'info>,
+    escrow_authority: &AccountInfo<'info>,
+    escrow_authority_bump: u8,
+    parent_xft_account_info: &AccountInfo<'info>,
+    parent_xft_id: u64,
     xft_id: u64,
 ) -> Result<()> {
-    let data = &mut parent_xft_account_info.try_borrow_mut_data()?;
-    let mut parent_xft_account = XftAccount::try_from_slice(&data)?;
-    let mut parent_settings = parent_xft_account.settings.clone();
-    while parent_settings.len() <= 14 {
-        parent_settings.push(0);
-    }
-    let mut child_index = 14;
-    while child_index < parent_settings.len() && parent_settings[child_index] != 0 {
-        child_index += 1;
-    }
-    if child_index >= parent_settings.len() {
-        parent_settings.push(xft_id);
-    } else {
-        parent_settings[child_index] = xft_id;
-    }
-    parent_xft_account.settings = parent_settings;
-    parent_xft_account.serialize(&mut **data)?;
-    Ok(())
+    let signer_seeds: &[&[&[u8]]] = &[&[b"escrow_authority", &[escrow_authority_bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        minter_program.clone(),
+        minter::cpi::accounts::AddLabelChild {
+            market_authority: escrow_authority.clone(),
+            label_account: parent_xft_account_info.clone(),
+        },
+        signer_seeds,
+    );
+    minter::cpi::add_label_child(cpi_ctx, parent_xft_id, xft_id)
 }
 
-// Helper function to remove a child XFT from a parent XFT's settings array
-pub fn remove_child_from_parent(
-    parent_xft_account_info: &AccountInfo,
+// Helper function to remove a child XFT from a parent XFT's children list through xft-minter
+pub fn remove_child_from_parent<'info>(
+    minter_program: &AccountInfo<'info>,
+    escrow_authority: &AccountInfo<'info>,
+    escrow_authority_bump: u8,
+    parent_xft_account_info: &AccountInfo<'info>,
+    parent_xft_id: u64,
     xft_id: u64,
 ) -> Result<()> {
-    let data = &mut parent_xft_account_info.try_borrow_mut_data()?;
-    let mut parent_xft_account = XftAccount::try_from_slice(&data)?;
-    let mut parent_settings = parent_xft_account.settings.clone();
-    let mut found = false;
-    for i in 14..parent_settings.len() {
-        if parent_settings[i] == xft_id {
-            parent_settings[i] = 0;
-            found = true;
-            break;
-        }
-    }
-    if !found {
-        return Err(error!(MarketError::ParentAccountMismatch));
+    let signer_seeds: &[&[&[u8]]] = &[&[b"escrow_authority", &[escrow_authority_bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        minter_program.clone(),
+        minter::cpi::accounts::RemoveLabelChild {
+            market_authority: escrow_authority.clone(),
+            label_account: parent_xft_account_info.clone(),
+        },
+        signer_seeds,
+    );
+    minter::cpi::remove_label_child(cpi_ctx, parent_xft_id, xft_id)
+}
+
+// Fails if the xft-admin pause registry has the global or market flag set
+fn require_market_not_paused(pause_registry: &AccountInfo) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(&[b"pause_registry"], &admin_xft::ID);
+    require!(pause_registry.key() == expected, MarketError::InvalidPauseRegistry);
+    if pause_registry.data_is_empty() {
+        return Ok(());
     }
-    parent_xft_account.settings = parent_settings;
-    parent_xft_account.serialize(&mut **data)?;
+    require!(*pause_registry.owner == admin_xft::ID, MarketError::InvalidPauseRegistry);
+    let data = pause_registry.try_borrow_data()?;
+    let registry = admin_xft::PauseRegistry::try_deserialize(&mut &data[..])?;
+    require!(!registry.global && !registry.market, MarketError::MarketPaused);
+    Ok(())
+}
+
+// Fails if `address` has an xft-admin blocklist entry; the entry PDA must be passed and empty
+fn require_not_blocked(blocklist_entry: &AccountInfo, address: &Pubkey) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(&[b"blocklist", address.as_ref()], &admin_xft::ID);
+    require!(blocklist_entry.key() == expected, MarketError::InvalidBlocklistEntry);
+    require!(blocklist_entry.data_is_empty(), MarketError::AddressBlocked);
     Ok(())
 }
 
@@ -452,27 +648,60 @@ fn pay_sol<'a>(from: &AccountInfo<'a>, to: &AccountInfo<'a>, amount: u64) -> Res
     Ok(())
 }
 
-// Internal helper to pay SPL tokens from one account to another
+// Internal helper to pay SPL tokens from one account to another, `hook_accounts` are the
+// transfer hook accounts of the payment mint
 fn pay_spl<'a>(
     from: &AccountInfo<'a>,
+    mint: &InterfaceAccount<'a, Mint>,
     to: &AccountInfo<'a>,
     authority: &AccountInfo<'a>,
     token_program: &AccountInfo<'a>,
+    hook_accounts: &[AccountInfo<'a>],
     amount: u64,
 ) -> Result<()> {
-    let cpi_accounts = token::Transfer {
+    let cpi_accounts = token_interface::TransferChecked {
         from: from.clone(),
+        mint: mint.to_account_info(),
         to: to.clone(),
         authority: authority.clone(),
     };
-    let cpi_ctx = CpiContext::new(token_program.clone(), cpi_accounts);
-    token::transfer(cpi_ctx, amount)?;
+    let cpi_ctx = CpiContext::new(token_program.clone(), cpi_accounts)
+        .with_remaining_accounts(hook_accounts.to_vec());
+    transfer_checked_with_hook(cpi_ctx, amount, mint.decimals)?;
     Ok(())
 }
 
+// Sends `amount` listed tokens out of the market escrow, signed by the escrow authority PDA.
+// Remaining accounts carry the transfer hook accounts of restricted mints.
+#[allow(clippy::too_many_arguments)]
+fn release_from_escrow<'info>(
+    escrow: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    escrow_authority: &AccountInfo<'info>,
+    escrow_authority_bump: u8,
+    token_program: &Interface<'info, TokenInterface>,
+    remaining_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[b"escrow_authority", &[escrow_authority_bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token_interface::TransferChecked {
+            from: escrow.to_account_info(),
+            mint: mint.to_account_info(),
+            to: to.to_account_info(),
+            authority: escrow_authority.clone(),
+        },
+        signer_seeds,
+    )
+    .with_remaining_accounts(remaining_accounts.to_vec());
+    transfer_checked_with_hook(cpi_ctx, amount, mint.decimals)
+}
+
 // Helper to map a Pubkey in listing.addresses to the correct AccountInfo in the Buy context
 fn get_account_info_for_pubkey<'info>(
-    ctx: &Context<Buy<'info>>,
+    ctx: &Context<'_, '_, '_, 'info, Buy<'info>>,
     key: &Pubkey,
 ) -> Option<AccountInfo<'info>> {
     if key == ctx.accounts.seller_payout.key {
@@ -498,17 +727,28 @@ pub enum MarketError {
     ListingNotActive,
     InsufficientQuantity,
     ParentAccountMismatch,
+    InvalidPauseRegistry,
+    MarketPaused,
+    PaymentMintNotAccepted,
+    InvalidTreasury,
+    InvalidBlocklistEntry,
+    AddressBlocked,
+    InvalidLabelAccount,
+    LabelNotReclaimable,
+    InvalidEdition,
+    InvalidListedMint,
 }
 
 #[derive(Accounts)]
+#[instruction(xft_id: u64)]
 pub struct Sell<'info> {
     #[account(mut)]
     pub seller: Signer<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    /// CHECK: minter XftAccount of the listing label (settings[0]), checked in XftAccount::load
     #[account(mut)]
     pub parent_xft_account: AccountInfo<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
-    #[account(mut)]
+    /// CHECK: minter XftAccount PDA for xft_id
+    #[account(mut, seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump, seeds::program = minter::ID)]
     pub xft_account: AccountInfo<'info>,
     /// CHECK: This is safe for prototyping; actual checks should be implemented in production
     #[account(mut)]
@@ -519,12 +759,29 @@ pub struct Sell<'info> {
     /// CHECK: This is safe for prototyping; actual checks should be implemented in production
     #[account(mut)]
     pub label_account: AccountInfo<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
-    #[account(mut)]
-    pub token_program: AccountInfo<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
-    #[account(mut)]
-    pub seller_xft_token_account: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Listed mint, the minter xft_mint PDA or the edition's own mint, checked in sell
+    pub xft_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = xft_mint,
+        token::authority = seller,
+        token::token_program = token_program
+    )]
+    pub seller_xft_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    /// CHECK: PDA holding listed XFTs until they are bought or the listing is cancelled
+    #[account(seeds = [b"escrow_authority"], bump)]
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = xft_mint,
+        associated_token::authority = escrow_authority,
+        associated_token::token_program = token_program
+    )]
+    pub listing_escrow: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
     /// CHECK: This is safe for prototyping; actual checks should be implemented in production
     #[account(mut)]
     pub listing_account: AccountInfo<'info>,
@@ -540,6 +797,19 @@ pub struct Sell<'info> {
     /// CHECK: Platform payout account
     #[account(mut)]
     pub platform_payout: AccountInfo<'info>,
+    /// CHECK: xft-admin pause registry PDA, checked in require_market_not_paused
+    pub pause_registry: AccountInfo<'info>,
+    /// xft-admin payment mint entry, required when settings[14] = 2
+    #[account(
+        seeds = [b"payment_mint", payment_mint.mint.as_ref()],
+        bump = payment_mint.bump,
+        seeds::program = admin_xft::ID
+    )]
+    pub payment_mint: Option<Account<'info, admin_xft::PaymentMint>>,
+    /// CHECK: xft-admin blocklist PDA for seller, checked in require_not_blocked
+    pub seller_blocklist_entry: AccountInfo<'info>,
+    /// CHECK: minter Edition record for edition listings (settings[15]), checked in Edition::load
+    pub edition: Option<AccountInfo<'info>>,
 }
 
 #[derive(Accounts)]
@@ -549,9 +819,12 @@ pub struct EditSell<'info> {
     /// CHECK: This is safe for prototyping; actual checks should be implemented in production
     #[account(mut)]
     pub listing_account: AccountInfo<'info>,
+    /// CHECK: xft-admin pause registry PDA, checked in require_market_not_paused
+    pub pause_registry: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
+#[instruction(xft_id: u64)]
 pub struct Buy<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
@@ -567,8 +840,8 @@ pub struct Buy<'info> {
     /// CHECK: This is safe for prototyping; actual checks should be implemented in production
     #[account(mut)]
     pub admin_account: AccountInfo<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
-    #[account(mut)]
+    /// CHECK: minter XftAccount PDA for xft_id
+    #[account(mut, seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump, seeds::program = minter::ID)]
     pub xft_account: AccountInfo<'info>,
     /// CHECK: This is safe for prototyping; actual checks should be implemented in production
     #[account(mut)]
@@ -597,6 +870,57 @@ pub struct Buy<'info> {
     /// CHECK: Parent XFT account for child/parent logic
     #[account(mut)]
     pub parent_xft_account: AccountInfo<'info>,
+    /// CHECK: xft-admin pause registry PDA, checked in require_market_not_paused
+    pub pause_registry: AccountInfo<'info>,
+    /// xft-admin payment mint entry, required for spl listings
+    #[account(
+        seeds = [b"payment_mint", payment_mint.mint.as_ref()],
+        bump = payment_mint.bump,
+        seeds::program = admin_xft::ID
+    )]
+    pub payment_mint: Option<Account<'info, admin_xft::PaymentMint>>,
+    /// Payment token mint, required for spl listings
+    pub payment_token_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub buyer_payment_token_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    /// xft-admin fee treasury for the listing's payment mint
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump = treasury.bump,
+        seeds::program = admin_xft::ID
+    )]
+    pub treasury: Account<'info, admin_xft::Treasury>,
+    /// CHECK: xft-admin treasury token account, validated by xft-admin::deposit_fee
+    #[account(mut)]
+    pub treasury_token_account: Option<AccountInfo<'info>>,
+    /// CHECK: xft-admin blocklist PDA for buyer, checked in require_not_blocked
+    pub buyer_blocklist_entry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for seller, checked in require_not_blocked
+    pub seller_blocklist_entry: AccountInfo<'info>,
+    /// Listed mint, checked against the listing's addresses[6]
+    pub xft_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: PDA holding listed XFTs, signs the release to the buyer
+    #[account(seeds = [b"escrow_authority"], bump)]
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        associated_token::mint = xft_mint,
+        associated_token::authority = escrow_authority,
+        associated_token::token_program = xft_token_program
+    )]
+    pub listing_escrow: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = xft_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = xft_token_program
+    )]
+    pub buyer_xft_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    /// Token program of the listed mint, token_program stays the payment mint's program
+    pub xft_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 #[derive(Accounts)]
@@ -609,6 +933,139 @@ pub struct CancelSell<'info> {
     /// CHECK: Parent XFT account for child/parent logic
     #[account(mut)]
     pub parent_xft_account: AccountInfo<'info>,
+    /// xft-minter program, updates the parent's children
+    pub xft_minter_program: Program<'info, minter::program::Minter>,
+    /// CHECK: xft-admin pause registry PDA, checked in require_market_not_paused
+    pub pause_registry: AccountInfo<'info>,
+    /// Listed mint, checked against the listing's addresses[6]
+    pub xft_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: PDA holding listed XFTs, signs the return to the seller
+    #[account(seeds = [b"escrow_authority"], bump)]
+    pub escrow_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        associated_token::mint = xft_mint,
+        associated_token::authority = escrow_authority,
+        associated_token::token_program = token_program
+    )]
+    pub listing_escrow: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        mut,
+        token::mint = xft_mint,
+        token::authority = seller,
+        token::token_program = token_program
+    )]
+    pub seller_xft_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64)]
+pub struct StartReclaimAuction<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: minter XftAccount for xft_id, checked in XftAccount::load
+    pub label_account: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ReclaimAuction::LEN,
+        seeds = [b"reclaim_auction", xft_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub reclaim_auction: Account<'info, ReclaimAuction>,
+    /// CHECK: xft-admin program, used for CPI only
+    #[account(address = admin_xft::ID)]
+    pub xft_admin_program: AccountInfo<'info>,
+    /// CHECK: xft-admin AdminXFT account, validated by xft-admin::get_fees
+    pub admin_account: AccountInfo<'info>,
+    /// CHECK: xft-admin pause registry PDA, checked in require_market_not_paused
+    pub pause_registry: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64)]
+pub struct ClaimReclaimAuction<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"reclaim_auction", xft_id.to_le_bytes().as_ref()],
+        bump = reclaim_auction.bump,
+        close = creator
+    )]
+    pub reclaim_auction: Account<'info, ReclaimAuction>,
+    /// CHECK: receives the auction rent back
+    #[account(mut, address = reclaim_auction.creator)]
+    pub creator: AccountInfo<'info>,
+    /// CHECK: minter XftAccount for xft_id, verified by minter::reclaim_label
+    #[account(mut)]
+    pub label_account: AccountInfo<'info>,
+    /// CHECK: label vault, verified by minter::reclaim_label; omit when the label has none
+    #[account(mut)]
+    pub vault: Option<AccountInfo<'info>>,
+    /// CHECK: PDA signing minter::reclaim_label
+    #[account(seeds = [b"reclaim_authority"], bump)]
+    pub market_authority: AccountInfo<'info>,
+    /// CHECK: minter vault authority PDA, verified by minter::reclaim_label
+    pub vault_authority: AccountInfo<'info>,
+    pub xft_minter_program: Program<'info, minter::program::Minter>,
+    /// CHECK: xft-vault program, verified by minter::reclaim_label
+    pub vault_program: AccountInfo<'info>,
+    /// CHECK: owner with unclaimed contents from an earlier reclaim, verified by
+    /// xft-vault::hand_over_vault; omit when nothing is claimable
+    #[account(mut)]
+    pub previous_claimant: Option<AccountInfo<'info>>,
+    /// CHECK: xft-admin program, used for CPI only
+    #[account(address = admin_xft::ID)]
+    pub xft_admin_program: AccountInfo<'info>,
+    /// xft-admin SOL fee treasury
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump = treasury.bump,
+        seeds::program = admin_xft::ID
+    )]
+    pub treasury: Account<'info, admin_xft::Treasury>,
+    /// CHECK: xft-admin pause registry PDA, checked in require_market_not_paused
+    pub pause_registry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for buyer, checked in require_not_blocked
+    pub buyer_blocklist_entry: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Declining-price auction for an expired lead or profile label
+#[account]
+pub struct ReclaimAuction {
+    pub xft_id: u64,
+    pub previous_owner: Pubkey,
+    pub start_price: u64,
+    pub floor_price: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub creator: Pubkey,
+    pub bump: u8,
+}
+
+impl ReclaimAuction {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 32 + 1;
+    pub const DURATION: i64 = 7 * 86400;
+    /// Opening price as a multiple of the yearly mint fee
+    pub const START_PRICE_MULTIPLE: u64 = 100;
+    /// Registration years the winner gets for the auction price
+    pub const REGISTRATION_YEARS: u64 = 1;
+
+    /// Falls linearly from start_price to floor_price over the auction, then stays at the floor
+    pub fn current_price(&self, now: i64) -> u64 {
+        if now >= self.end_time {
+            return self.floor_price;
+        }
+        let elapsed = now.saturating_sub(self.start_time).max(0) as u128;
+        let duration = (self.end_time - self.start_time) as u128;
+        let drop = (self.start_price - self.floor_price) as u128 * elapsed / duration;
+        self.start_price - drop as u64
+    }
 }
 
 // Listing struct for storing listing data
@@ -659,10 +1116,58 @@ pub struct PurchaseCompleted {
     pub buyer: Pubkey,
     pub seller: Pubkey,
     pub xft_id: u64,
+    /// Numbered edition sold, 0 for non-edition listings
+    pub edition: u64,
     pub quantity: u64,
     pub total_cost: u64,
 }
 
+#[event]
+pub struct ReclaimAuctionStarted {
+    pub xft_id: u64,
+    pub previous_owner: Pubkey,
+    pub start_price: u64,
+    pub floor_price: u64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct ReclaimAuctionSettled {
+    pub xft_id: u64,
+    pub winner: Pubkey,
+    pub previous_owner: Pubkey,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+// Local mirror of minter::Edition
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct Edition {
+    pub xft_id: u64,
+    pub number: u64,
+    pub max_supply: u64,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub bump: u8,
+}
+
+impl Edition {
+    /// Reads the minter Edition record for (xft_id, number), checking its PDA
+    pub fn load(account: &AccountInfo, xft_id: u64, number: u64) -> Result<Self> {
+        require!(*account.owner == minter::ID, MarketError::InvalidEdition);
+        let data = account.try_borrow_data()?;
+        let edition = Edition::deserialize(&mut &data[8..]).map_err(|_| error!(MarketError::InvalidEdition))?;
+        require!(edition.xft_id == xft_id && edition.number == number, MarketError::InvalidEdition);
+        let expected = Pubkey::create_program_address(
+            &[b"edition", xft_id.to_le_bytes().as_ref(), number.to_le_bytes().as_ref(), &[edition.bump]],
+            &minter::ID,
+        )
+        .map_err(|_| error!(MarketError::InvalidEdition))?;
+        require!(account.key() == expected, MarketError::InvalidEdition);
+        Ok(edition)
+    }
+}
+
 // Add a local definition for XftAccount for deserialization
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct XftAccount {
@@ -672,3 +1177,73 @@ pub struct XftAccount {
     pub ipfs: String,
     pub bump: u8,
 }
+
+impl XftAccount {
+    /// Reads the minter XftAccount for xft_id, checking it sits at its [b"xft", xft_id] PDA
+    pub fn load(account: &AccountInfo, xft_id: u64) -> Result<Self> {
+        require!(*account.owner == minter::ID, MarketError::InvalidLabelAccount);
+        let data = account.try_borrow_data()?;
+        let xft_account = XftAccount::deserialize(&mut &data[8..]).map_err(|_| error!(MarketError::InvalidLabelAccount))?;
+        require!(xft_account.xft_id == xft_id, MarketError::InvalidLabelAccount);
+        let expected = xft_account_address(&minter::ID, xft_id, xft_account.bump).ok_or(MarketError::InvalidLabelAccount)?;
+        require!(account.key() == expected, MarketError::InvalidLabelAccount);
+        Ok(xft_account)
+    }
+
+    pub fn xft_settings(&self) -> Result<XftSettings> {
+        XftSettings::decode(&self.settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auction(start_price: u64, floor_price: u64, start_time: i64) -> ReclaimAuction {
+        ReclaimAuction {
+            xft_id: 1,
+            previous_owner: Pubkey::default(),
+            start_price,
+            floor_price,
+            start_time,
+            end_time: start_time + ReclaimAuction::DURATION,
+            creator: Pubkey::default(),
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn current_price_starts_at_start_price() {
+        let a = auction(700_000, 7_000, 1_000);
+        assert_eq!(a.current_price(1_000), 700_000);
+        // Clock skew before the start never prices above start_price
+        assert_eq!(a.current_price(0), 700_000);
+        assert_eq!(a.current_price(i64::MIN), 700_000);
+    }
+
+    #[test]
+    fn current_price_falls_linearly() {
+        let a = auction(700_000, 7_000, 1_000);
+        let day = 86_400;
+        assert_eq!(a.current_price(1_000 + day), 601_000);
+        assert_eq!(a.current_price(1_000 + ReclaimAuction::DURATION / 2), 353_500);
+        assert_eq!(a.current_price(a.end_time - day), 106_000);
+        assert!(a.current_price(a.end_time - 1) > a.floor_price);
+    }
+
+    #[test]
+    fn current_price_stays_at_floor_after_end() {
+        let a = auction(700_000, 7_000, 1_000);
+        assert_eq!(a.current_price(a.end_time), 7_000);
+        assert_eq!(a.current_price(a.end_time + 1), 7_000);
+        assert_eq!(a.current_price(i64::MAX), 7_000);
+    }
+
+    #[test]
+    fn current_price_handles_flat_auction() {
+        let a = auction(5_000, 5_000, 1_000);
+        assert_eq!(a.current_price(1_000), 5_000);
+        assert_eq!(a.current_price(1_000 + ReclaimAuction::DURATION / 2), 5_000);
+        assert_eq!(a.current_price(a.end_time), 5_000);
+    }
+}
@@ -1,11 +1,27 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke;
-use anchor_spl::token::{self, TokenAccount, Token, Mint, Transfer};
+use anchor_lang::system_program;
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken};
+use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_2022::spl_token_2022::{self, extension::ExtensionType, instruction::AuthorityType};
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::Field;
+use anchor_spl::token_2022_extensions::{
+    metadata_pointer_initialize, non_transferable_mint_initialize, permanent_delegate_initialize,
+    token_metadata_initialize, token_metadata_update_field, transfer_hook_initialize,
+    MetadataPointerInitialize, NonTransferableMintInitialize, PermanentDelegateInitialize,
+    TokenMetadataInitialize, TokenMetadataUpdateField, TransferHookInitialize,
+};
+use anchor_spl::token_interface::{self, Token2022, TokenInterface};
+use std::str::FromStr;
+use xft_types::{
+    active_operator_role, canonical_title, normalize_title, title_seed, transfer_checked_with_hook, xft_account_address,
+    LabelType, TransferRule, XftSettings,
+};
 declare_program!(operator);
 declare_program!(vault);
+declare_program!(xft_transfer_hook);
 use operator::cpi::{self as operator_cpi, accounts::IsOperator};
-use vault::cpi::{self as vault_cpi, accounts::CreateVault};
-use anchor_lang::system_program;
+use vault::cpi::{self as vault_cpi, accounts::{CloseEditionVault, CloseVault, CreateEditionVault, CreateVault, HandOverVault}};
 declare_program!(admin_xft);
 
 declare_id!("BPFLoaderUpgradeab1e11111111111111111111111");
@@ -21,8 +37,90 @@ pub enum ErrorCode {
     TitleAlreadyExists,
     #[msg("Invalid XFT type for wrapping")] 
     InvalidXftType,
+    #[msg("Invalid pause registry account")]
+    InvalidPauseRegistry,
+    #[msg("Minting is paused")]
+    MintingPaused,
+    #[msg("Transfers are paused")]
+    TransfersPaused,
+    #[msg("Payment mint is not accepted")]
+    PaymentMintNotAccepted,
+    #[msg("Treasury does not match the payment mint")]
+    InvalidTreasury,
+    #[msg("Invalid blocklist entry account")]
+    InvalidBlocklistEntry,
+    #[msg("Address is on the xft-admin blocklist")]
+    AddressBlocked,
+    #[msg("Invalid title reservation account")]
+    InvalidTitleReservation,
+    #[msg("Title is reserved for another wallet")]
+    TitleReserved,
+    #[msg("Label account is not the XFT account for this xft_id")]
+    InvalidLabelAccount,
+    #[msg("Only the previous owner can renew during the grace period")]
+    GracePeriodOwnerOnly,
+    #[msg("Grace period is over, the label can only be reclaimed through the market")]
+    GracePeriodOver,
+    #[msg("Label is not expired past its grace period")]
+    LabelNotReclaimable,
+    #[msg("Vault account does not match the label vault")]
+    InvalidVault,
+    #[msg("Token account is not the associated token account for this mint")]
+    InvalidTokenAccount,
+    #[msg("XFT is soulbound")]
+    NotTransferable,
+    #[msg("Only restricted XFTs can be recalled")]
+    NotRecallable,
+    #[msg("Title lookup does not belong to this XFT")]
+    InvalidTitleLookup,
+    #[msg("Label still has children listed under it")]
+    HasChildren,
+    #[msg("Metadata is frozen")]
+    MetadataFrozen,
+    #[msg("Metadata URI is too long")]
+    MetadataUriTooLong,
+    #[msg("Only limited edition XFTs have printed editions")]
+    NotLimitedEdition,
+    #[msg("All editions have been printed")]
+    EditionSupplyExhausted,
+    #[msg("Limited editions are held as numbered editions, use the edition instructions")]
+    LimitedEdition,
+    #[msg("Title lookup already uses the normalized seed")]
+    TitleAlreadyMigrated,
+    #[msg("XFT is not wrapped into this account")]
+    NotWrapped,
+    #[msg("The wrapped supply is not complete")]
+    WrappedSupplyMismatch,
+    #[msg("Not a single token NFT mint")]
+    InvalidNft,
+    #[msg("Invalid Metaplex metadata account")]
+    InvalidNftMetadata,
+    #[msg("Soulbound and restricted XFTs need the Token-2022 program")]
+    Token2022Required,
+    #[msg("Restricted XFTs need the transfer hook accounts")]
+    MissingTransferHookAccounts,
+    #[msg("Fee schedule is not the xft-admin mint fee PDA for this label type")]
+    InvalidFeeSchedule,
+    #[msg("XFT is not listed under this label")]
+    NotLabelChild,
 }
 
+// Token-2022 metadata symbol of every XFT mint
+const XFT_SYMBOL: &str = "XFT";
+
+// Metadata URIs kept in MetadataHistory and the longest URI update_metadata accepts
+const MAX_METADATA_HISTORY: usize = 8;
+const MAX_METADATA_URI_LEN: usize = 200;
+
+// xft-market signs reclaim_label with its reclaim authority PDA
+const MARKET_PROGRAM_ID: &str = "JDmExoWsKJe7eMEcxaNKgBe1dgHXe5ns3wGqxgc7kAez";
+
+// xft-transfer-hook enforces the restricted transfer rule on Token-2022 mints
+const TRANSFER_HOOK_PROGRAM_ID: &str = "6LPXsdNeT9MJhFXwHkmH88rYgcdmz8tuVpGZLFgrntWz";
+
+// Metaplex Token Metadata, read by wrap_nft for the name and URI of external NFTs
+const METAPLEX_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
 // Event stub
 #[event]
 pub struct MintXftEvent {
@@ -46,95 +144,103 @@ pub struct XftAdmin {
 #[program]
 pub mod minter {
     use super::*;
-    
-    /// The settings vector configures labels:
-    /// * 0: link to label
-    /// * 1: registration in years
-    /// * 2: operator license
-    /// * 3: xft type
-    /// * 4: if type is license, license term
-    /// * 5: 0 false, 1 true (formerly mint pass)
-    /// * 6: quantity
-    /// * 7: label registration expire
-    /// * 8: if type is market license, marketplace fee percentage
-    /// * 9: transferable
-    /// * 10: wrapto
-    /// * 11: label split for marketplace license
-    /// * 12: label vault locked
-    /// * 13: label vault unlock date
-
-    /// Label Types (settings[3]):
-    /// * 1: Lead Label (1 of 1)
-    /// * 2: Profile Label (1 of 1)
-    /// * 3: Tag Label (must be limited edidtion)
-    /// * 4: Chapter Label (must be limited edidtion)
-    /// * 5: Operator License (must be limited edidtion)
-    /// * 6: Marketplace License (must be limited edidtion)
-    /// * 7: Art/tickets/gaming (can be 1 of 1 or limited edition)
-    /// * 8: wrappedTo (1 of 1)
-    /// * 9: open
 
+    /// The settings vector configures labels, see xft_types::XftSettings for the
+    /// slot layout and xft_types::LabelType for the label types (settings[3]).
+    ///
     /// Addresses
     /// address[0] create
     /// address[1] label owner
     /// address[2] vault address
-    
     pub fn mint_xft(
         ctx: Context<MintXft>,
         title: String,
         ipfs: String,
         settings: Vec<u64>,
     ) -> Result<()> {
-        // TODO: Implement logic to mint an XFT with metadata and settings
-        if settings[0] > 0 {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.minting, ErrorCode::MintingPaused);
+        require_not_blocked(&ctx.accounts.authority_blocklist_entry, &ctx.accounts.authority.key())?;
+        require_not_blocked(&ctx.accounts.label_owner_blocklist_entry, &ctx.accounts.label_owner.key())?;
+        require_not_blocked(&ctx.accounts.caller_blocklist_entry, &ctx.accounts.caller.key())?;
+        // Decode and validate settings based on label type
+        require!(ipfs.len() <= MAX_METADATA_URI_LEN, ErrorCode::MetadataUriTooLong);
+        let mut xft_settings = XftSettings::new(&settings)?;
+        // Expiry and license term come from the paid years, never from the caller
+        xft_settings.start_registration(Clock::get()?.unix_timestamp as u64);
+        let label_type = xft_settings.label_type;
+        let edition_size = xft_settings.quantity;
+        require!(edition_size > 0, ErrorCode::InvalidSettings);
+        if xft_settings.label_id > 0 {
             // This mint is linked to a label, check if caller is label owner
-            let is_owner = is_label_owner(ctx.accounts, ctx.accounts.authority.key(), settings[0])?;
+            let is_owner = label_owned_by(&ctx.accounts.label_account, ctx.accounts.authority.key(), xft_settings.label_id)?;
             if !is_owner {
-                check_operator_permission(&ctx, settings[0])?;
+                check_operator_permission(&ctx, xft_settings.label_id)?;
             }
         }
-        // Validate settings based on label type
-        let label_type = settings.get(3).copied().unwrap_or(0);
-        let edition_size = settings.get(6).copied().unwrap_or(0);
-        let label_id = settings.get(1).copied().unwrap_or(0);
-
-        // Check label type 1 (Lead Label) - must be 1 of 1
-        if label_type == 1 && edition_size != 1 {
-            return Err(ErrorCode::InvalidSettings.into());
-        }
-
-        // Check label types 3,4,5,6 (Tag, Chapter, Operator License, Marketplace License) - must be limited edition
-        if (label_type == 3 || label_type == 4 || label_type == 5 || label_type == 6) && edition_size <= 1 {
-            return Err(ErrorCode::InvalidSettings.into());
-        }
-
-        // Check non-7,8 label types must have label_id > 0
-        if label_type != 7 && label_type != 8 && label_type != 1 && label_id == 0 {
-            return Err(ErrorCode::InvalidSettings.into());
-        }
         
-        // Enforce on-chain uniqueness of title using PDA
-        let title_without_spaces: String = title.chars().filter(|c| !c.is_whitespace()).collect();
-        if !ctx.accounts.title_lookup.to_account_info().data_is_empty() {
-            return Err(ErrorCode::TitleAlreadyExists.into());
+        // Enforce on-chain uniqueness of label titles using a PDA seeded by the normalized
+        // title. Label titles must also stay inside the allowed character set. Items do not
+        // claim their title and pass no title lookup.
+        let normalized_title = if label_type.is_label() {
+            normalize_title(&title)?
+        } else {
+            canonical_title(&title)
+        };
+        match &ctx.accounts.title_lookup {
+            // init_if_needed leaves a fresh lookup zeroed, claimed lookups store their PDA bump
+            Some(title_lookup) if label_type.is_label() => {
+                require!(title_lookup.bump == 0, ErrorCode::TitleAlreadyExists);
+            }
+            None if !label_type.is_label() => {}
+            _ => return Err(ErrorCode::InvalidTitleLookup.into()),
+        }
+        // Reserved titles can only be minted by their designated wallet, premium titles
+        // replace the fee schedule price
+        let reservation = if label_type.is_label() {
+            TitleReservation::load(&ctx.accounts.title_reservation, &normalized_title)?
+        } else {
+            None
+        };
+        if let Some(reservation) = &reservation {
+            require!(
+                reservation.reserved_for == Pubkey::default() || reservation.reserved_for == ctx.accounts.authority.key(),
+                ErrorCode::TitleReserved
+            );
         }
-        // Get mint price per year and payto address from xft-admin via CPI
-        let mut mint_fee_per_year = 0u64;
-        let mut payout_address = Pubkey::default();
-        if label_type != 7 && label_type != 8 {
-            // CPI to xft-admin::get_fees
-            let cpi_program = ctx.accounts.admin_program.to_account_info();
-            let cpi_accounts = admin_xft_cpi::GetFees {
-            admin: ctx.accounts.admin_account.to_account_info(),
+        // Get the mint price from the xft-admin fee schedule and pay it into the xft-admin treasury
+        if label_type.is_label() {
+            let years = xft_settings.registration_years;
+            require_fee_schedule(&ctx.accounts.fee_schedule, label_type)?;
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.admin_program.to_account_info(),
+                admin_xft_cpi::GetMintFee {
+                    admin: ctx.accounts.admin_account.to_account_info(),
+                    fee_schedule: ctx.accounts.fee_schedule.to_account_info(),
+                },
+            );
+            let total_fee = match &reservation {
+                Some(reservation) if reservation.premium_fee_per_year > 0 => {
+                    reservation.premium_fee_per_year.saturating_mul(years)
+                }
+                _ => admin_xft_cpi::cpi::get_mint_fee(
+                    cpi_ctx,
+                    label_type.as_u64(),
+                    normalized_title.chars().count() as u64,
+                    years,
+                )?,
             };
-            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-            let (fee_per_year, _marketplace_fee, payto) = admin_xft_cpi::cpi::get_fees(cpi_ctx)?;
-            mint_fee_per_year = fee_per_year;
-            payout_address = payto;
-            let years = settings.get(1).copied().unwrap_or(1);
-            let total_fee = mint_fee_per_year.saturating_mul(years);
-            // Transfer lamports from payer to payout_address
-            transfer_lamports(&ctx.accounts.payer.to_account_info(), &ctx.accounts.payout_account, total_fee, &ctx.accounts.system_program)?;
+            pay_protocol_fee(
+                &ctx.accounts.admin_program,
+                &ctx.accounts.treasury,
+                &ctx.accounts.treasury_token_account,
+                &ctx.accounts.payer,
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.payer_payment_token_account,
+                &ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.system_program,
+                total_fee,
+            )?;
         }
             // Generate xft_id before creating vault
             let xft_id = ctx.accounts.counter.value;
@@ -144,49 +250,106 @@ pub mod minter {
             addresses[0] = ctx.accounts.authority.key();
             addresses[1] = ctx.accounts.label_owner.key();
             if edition_size == 1 {
-                // CPI to xft-vault::create_vault(xft_id, settings[3])
+                // CPI to xft-vault::create_vault(xft_id, label_type)
                 let cpi_program = ctx.accounts.vault_program.to_account_info();
                 let cpi_accounts = CreateVault {
                     vault: ctx.accounts.vault.to_account_info(),
                     authority: ctx.accounts.authority.to_account_info(),
                     system_program: ctx.accounts.system_program.to_account_info(),
-                    minter_program: ctx.accounts.admin_program.to_account_info(),
+                    minter_program: ctx.accounts.vault_program.to_account_info(),
                 };
                 let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-                let label_type = settings[3];
-                vault_cpi::create_vault(cpi_ctx, xft_id, label_type)?;
+                vault_cpi::create_vault(cpi_ctx, xft_id, label_type.as_u64())?;
                 addresses[2] = ctx.accounts.vault.key();
             }
         
             // Create the XFT account
             let xft_account = &mut ctx.accounts.xft;
             xft_account.xft_id = xft_id;
-            xft_account.settings = settings.clone();
+            xft_account.settings = xft_settings.encode();
             xft_account.addresses = addresses;
             xft_account.bump = ctx.bumps.xft;
-            xft_account.ipfs = ipfs;
-            // Transfer XFT to caller
-            let cpi_accounts = token::Transfer {
-                from: ctx.accounts.xft_token_account.to_account_info(),
-                to: ctx.accounts.caller_token_account.to_account_info(),
-                authority: ctx.accounts.caller.to_account_info(),
-            };
-            let cpi_ctx = CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                cpi_accounts,
+            xft_account.ipfs = ipfs.clone();
+            create_xft_mint(
+                &XftMintAccounts {
+                    payer: ctx.accounts.payer.to_account_info(),
+                    xft_mint: ctx.accounts.xft_mint.to_account_info(),
+                    mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                &[b"xft_mint", xft_id.to_le_bytes().as_ref(), &[ctx.bumps.xft_mint]],
+                ctx.bumps.mint_authority,
+                &title,
+                &ipfs,
+                xft_settings.transfer_rule,
+            )?;
+            // Persist the XftAccount now, xft-transfer-hook reads it while writing the
+            // extra-account-meta list of restricted mints
+            ctx.accounts.xft.exit(&crate::ID)?;
+            init_transfer_hook_metas(
+                TransferHookAccounts {
+                    payer: ctx.accounts.payer.to_account_info(),
+                    extra_account_meta_list: ctx.accounts.extra_account_meta_list.as_ref().map(|a| a.to_account_info()),
+                    mint: ctx.accounts.xft_mint.to_account_info(),
+                    xft_account: ctx.accounts.xft.to_account_info(),
+                    label_account: Some(ctx.accounts.label_account.to_account_info()),
+                    transfer_hook_program: ctx.accounts.transfer_hook_program.clone(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                xft_settings.transfer_rule,
+                xft_id,
+                0,
+            )?;
+            // Mint to the caller's associated token account, signed by the PDA mint authority
+            let expected_token_account = get_associated_token_address_with_program_id(
+                &ctx.accounts.caller.key(),
+                &ctx.accounts.xft_mint.key(),
+                &ctx.accounts.token_program.key(),
             );
-            token::transfer(cpi_ctx, settings[6])?;
-
-            if settings[3] != 7 && settings[3] != 8 {
-                // Store title without spaces for lookup
-                let (title_lookup_pda, bump) = Pubkey::find_program_address(
-                    &[b"title_lookup", title_without_spaces.as_bytes()],
-                    ctx.program_id
+            require!(ctx.accounts.caller_token_account.key() == expected_token_account, ErrorCode::InvalidTokenAccount);
+            associated_token::create(CpiContext::new(
+                ctx.accounts.associated_token_program.to_account_info(),
+                associated_token::Create {
+                    payer: ctx.accounts.payer.to_account_info(),
+                    associated_token: ctx.accounts.caller_token_account.to_account_info(),
+                    authority: ctx.accounts.caller.to_account_info(),
+                    mint: ctx.accounts.xft_mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+            ))?;
+            // 1 of 1 items are minted here and can never be minted again. Limited editions are
+            // printed one numbered edition at a time by print_edition, each with its own mint;
+            // their master mint holds no tokens and keeps the PDA mint authority.
+            if edition_size == 1 {
+                let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", &[ctx.bumps.mint_authority]]];
+                let cpi_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::MintTo {
+                        mint: ctx.accounts.xft_mint.to_account_info(),
+                        to: ctx.accounts.caller_token_account.to_account_info(),
+                        authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                );
+                token_interface::mint_to(cpi_ctx, 1)?;
+                let cpi_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::SetAuthority {
+                        current_authority: ctx.accounts.mint_authority.to_account_info(),
+                        account_or_mint: ctx.accounts.xft_mint.to_account_info(),
+                    },
+                    signer_seeds,
                 );
-                let title_lookup_account = &mut ctx.accounts.title_lookup;
+                token_interface::set_authority(cpi_ctx, AuthorityType::MintTokens, None)?;
+            }
+
+            if label_type.is_label() {
+                // Claim the normalized title lookup
+                let title_lookup_account = ctx.accounts.title_lookup.as_mut().ok_or(ErrorCode::InvalidTitleLookup)?;
                 title_lookup_account.xft_id = xft_id;
-                title_lookup_account.bump = bump;
-            
+                title_lookup_account.bump = ctx.bumps.title_lookup.ok_or(ErrorCode::InvalidTitleLookup)?;
             }
             // Emit mint event
             emit!(MintXftEvent {
@@ -194,32 +357,50 @@ pub mod minter {
                 caller: ctx.accounts.caller.key(),
                 label_owner: ctx.accounts.label_owner.key(),
                 title: title.clone(),
-                label_type: label_type,
+                label_type: label_type.as_u64(),
                 edition_size: edition_size,
-                label_id: label_id,
-                registration_expire: settings[7],
+                label_id: xft_settings.label_id,
+                registration_expire: xft_settings.registration_expire,
                 timestamp: Clock::get()?.unix_timestamp,
             });
             Ok(())
     }
 
-    pub fn wrap_xft(ctx: Context<WrapXft>) -> Result<()> {
-        let parent_xft_id;
-        {
-            let parent_xft = &ctx.accounts.parent_xft;
-            parent_xft_id = parent_xft.xft_id;
-            require!(parent_xft.settings.len() > 10 && parent_xft.settings[10] != 0, ErrorCode::InvalidXftType);
-            require!(parent_xft.settings[3] == 8 || parent_xft.settings[3] == 4, ErrorCode::InvalidXftType);
-        }
+    /// Wraps the parent XFT into a new XFT: the parent tokens go into the wrap escrow until
+    /// unwrap_xft, the wrapped supply is minted from the wrapped xft_id's own mint PDA to the
+    /// wrapper, the wrapped vault is created and the child id is written into parent
+    /// settings[11]. Restricted parents need the transfer-hook accounts as remaining accounts.
+    pub fn wrap_xft<'info>(ctx: Context<'_, '_, '_, 'info, WrapXft<'info>>) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.minting, ErrorCode::MintingPaused);
+        let parent_xft_id = ctx.accounts.parent_xft.xft_id;
+        let mut parent_settings = ctx.accounts.parent_xft.xft_settings()?;
+        require!(parent_settings.wrap_to != 0, ErrorCode::InvalidXftType);
+        require!(
+            parent_settings.label_type == LabelType::WrappedTo || parent_settings.label_type == LabelType::Chapter,
+            ErrorCode::InvalidXftType
+        );
+        // A limited edition master mint holds no tokens, its editions are separate mints
+        require!(parent_settings.quantity == 1, ErrorCode::LimitedEdition);
+        let wrapped_quantity = parent_settings.quantity;
         // Generate new XFT ID for the wrapped version
         let counter = &mut ctx.accounts.counter;
         let new_xft_id = counter.value;
         counter.value += 1;
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.vault_program.to_account_info(),
+            CreateVault {
+                vault: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                minter_program: ctx.accounts.vault_program.to_account_info(),
+            },
+        );
+        vault_cpi::create_vault(cpi_ctx, new_xft_id, parent_settings.label_type.as_u64())?;
         // Create wrapped XFT with same settings and addresses as parent
-        let wrapped_settings = ctx.accounts.parent_xft.settings.clone();
+        let wrapped_settings = parent_settings.encode();
         let mut wrapped_addresses = ctx.accounts.parent_xft.addresses.clone();
-        // CPI call to create_vault (assume correct context, or add as needed)
-        // For now, just set vault address
+        wrapped_addresses.truncate(3);
         wrapped_addresses[2] = ctx.accounts.vault.key();
         // Create the wrapped XFT account
         let wrapped_xft = &mut ctx.accounts.wrapped_xft;
@@ -229,35 +410,94 @@ pub mod minter {
         wrapped_xft.bump = ctx.bumps.wrapped_xft;
         wrapped_xft.ipfs = ctx.accounts.parent_xft.ipfs.clone();
         // Store parent to child relationship in parent's settings
-        let parent_xft_mut = &mut ctx.accounts.parent_xft;
-        let mut parent_settings = parent_xft_mut.settings.clone();
-        if parent_settings.len() <= 11 {
-            while parent_settings.len() <= 11 {
-                parent_settings.push(0);
-            }
-        }
-        parent_settings[11] = new_xft_id;
-        parent_xft_mut.settings = parent_settings;
-        // Transfer XFT from user to burn address using anchor-spl
+        parent_settings.label_split = new_xft_id;
+        ctx.accounts.parent_xft.set_xft_settings(&parent_settings);
+        // Move the parent XFT from the user into the wrap escrow
         let transfer_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
+            token_interface::TransferChecked {
                 from: ctx.accounts.user_xft_token_account.to_account_info(),
-                to: ctx.accounts.burn_address.to_account_info(),
+                mint: ctx.accounts.parent_mint.to_account_info(),
+                to: ctx.accounts.wrap_escrow_token_account.to_account_info(),
                 authority: ctx.accounts.wrapper.to_account_info(),
             },
-        );
-        token::transfer(transfer_ctx, ctx.accounts.parent_xft.settings[1])?;
-        // Mint new wrapped XFT tokens to user using anchor-spl
-        let mint_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            token::MintTo {
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        transfer_checked_with_hook(transfer_ctx, wrapped_quantity, 0)?;
+        // The wrapped mint is a minter PDA like every XFT mint, its supply is fixed to the
+        // escrowed quantity so only the full wrapped supply can unwrap the parent
+        let ipfs = ctx.accounts.parent_xft.ipfs.clone();
+        create_xft_mint(
+            &XftMintAccounts {
+                payer: ctx.accounts.authority.to_account_info(),
+                xft_mint: ctx.accounts.wrapped_mint.to_account_info(),
+                mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            &[b"xft_mint", new_xft_id.to_le_bytes().as_ref(), &[ctx.bumps.wrapped_mint]],
+            ctx.bumps.mint_authority,
+            &format!("Wrapped XFT {}", parent_xft_id),
+            &ipfs,
+            parent_settings.transfer_rule,
+        )?;
+        ctx.accounts.wrapped_xft.exit(&crate::ID)?;
+        init_transfer_hook_metas(
+            TransferHookAccounts {
+                payer: ctx.accounts.authority.to_account_info(),
+                extra_account_meta_list: ctx.accounts.extra_account_meta_list.as_ref().map(|a| a.to_account_info()),
                 mint: ctx.accounts.wrapped_mint.to_account_info(),
-                to: ctx.accounts.user_wrapped_token_account.to_account_info(),
-                authority: ctx.accounts.wrapper.to_account_info(),
+                xft_account: ctx.accounts.wrapped_xft.to_account_info(),
+                label_account: ctx.accounts.label_account.clone(),
+                transfer_hook_program: ctx.accounts.transfer_hook_program.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
             },
+            parent_settings.transfer_rule,
+            new_xft_id,
+            0,
+        )?;
+        let expected_token_account = get_associated_token_address_with_program_id(
+            &ctx.accounts.wrapper.key(),
+            &ctx.accounts.wrapped_mint.key(),
+            &ctx.accounts.token_program.key(),
         );
-        token::mint_to(mint_ctx, ctx.accounts.parent_xft.settings[1])?;
+        require!(ctx.accounts.user_wrapped_token_account.key() == expected_token_account, ErrorCode::InvalidTokenAccount);
+        associated_token::create(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.authority.to_account_info(),
+                associated_token: ctx.accounts.user_wrapped_token_account.to_account_info(),
+                authority: ctx.accounts.wrapper.to_account_info(),
+                mint: ctx.accounts.wrapped_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", &[ctx.bumps.mint_authority]]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.wrapped_mint.to_account_info(),
+                    to: ctx.accounts.user_wrapped_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            wrapped_quantity,
+        )?;
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::SetAuthority {
+                    current_authority: ctx.accounts.mint_authority.to_account_info(),
+                    account_or_mint: ctx.accounts.wrapped_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
         emit!(WrappedXftEvent {
             parent_xft_id,
             wrapped_xft_id: new_xft_id,
@@ -268,67 +508,819 @@ pub mod minter {
         Ok(())
     }
     
-    pub fn transfer_xft(ctx: Context<TransferXft>) -> Result<()> {
-        let xft_account = &ctx.accounts.xft_account;
-        let settings = &xft_account.settings;
-        let addresses = &xft_account.addresses;
-        // Get the settings[0] account of the XFT
-        let settings_0_xft_id = settings[0];
-        let parent_xft_account = if settings_0_xft_id > 0 {
-            XftAccount::try_from_slice(&ctx.accounts.parent_xft_account.data.borrow())?
+    pub fn transfer_xft<'info>(ctx: Context<'_, '_, '_, 'info, TransferXft<'info>>) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.transfers, ErrorCode::TransfersPaused);
+        require_not_blocked(&ctx.accounts.caller_blocklist_entry, &ctx.accounts.caller.key())?;
+        require_not_blocked(&ctx.accounts.receiver_blocklist_entry, &ctx.accounts.receiver.key())?;
+        let settings = ctx.accounts.xft_account.xft_settings()?;
+        // Get the label account the XFT is linked to
+        let label_id = settings.label_id;
+        let parent_xft_account = if label_id > 0 {
+            load_xft_account(&ctx.accounts.parent_xft_account, label_id)?
         } else {
             return Err(ErrorCode::InvalidSettings.into());
         };
-        
-        // Check if this is a limited transfer XFT (settings[9] = 0)
-        let mut is_authorized_sender = false;
-        if settings[9] == 0 {
-            // This XFT can only be sent by addresses[0] or addresses[1] of the settings[0] XFT
-            is_authorized_sender = parent_xft_account.addresses[0] == ctx.accounts.caller.key() || 
-                                     parent_xft_account.addresses[1] == ctx.accounts.caller.key();
-            if !is_authorized_sender {
-                // Check if caller is an operator for the parent XFT
-                let cpi_accounts = operator::cpi::accounts::IsOperator {
-                    operator_account: ctx.accounts.parent_xft_account.to_account_info(),
-                };
-                let cpi_ctx = CpiContext::new(ctx.accounts.operator_program.to_account_info(), cpi_accounts);
-                // Anchor CPI does not return values; in real use, check account state or use events
-                operator::cpi::is_operator(
-                    cpi_ctx,
-                    ctx.accounts.caller.key(),
-                    settings_0_xft_id,
-                )?;
-                // If you need to check the result, fetch the account state here
-                // For now, we cannot set is_authorized_sender = true based on CPI return
-            }
-            if !is_authorized_sender {
-                // If caller is not authorized, it can only be sent to the burn address
-                require!(
-                    ctx.accounts.receiver.key() == ctx.accounts.burn_address.key(),
-                    ErrorCode::Unauthorized
-                );
-            }
-        }
-        // If settings[6] of the xft_id == 1 and settings[0] == 0
-        if settings[6] == 1 && settings[0] == 0 {
+        // Limited editions move one numbered edition at a time with transfer_edition
+        require!(settings.quantity == 1, ErrorCode::LimitedEdition);
+        // Soulbound XFTs never move; restricted ones only from the label creator, owner or an
+        // active operator of the label. Anyone else can only destroy the item with burn_xft
+        require_can_send(
+            &settings,
+            &ctx.accounts.parent_xft_account,
+            ctx.accounts.operator_account.as_ref(),
+            ctx.accounts.caller.key(),
+        )?;
+        // 1 of 1 XFTs that are not linked to a label move their ownership with the token
+        if settings.quantity == 1 && settings.label_id == 0 {
             // Update addresses[1] to receiver
-            let mut parent_xft_account = XftAccount::try_from_slice(&ctx.accounts.parent_xft_account.data.borrow())?;
+            let mut parent_xft_account = parent_xft_account;
             parent_xft_account.addresses[1] = ctx.accounts.receiver.key();
             // Update the parent XFT account data
             let mut data = ctx.accounts.parent_xft_account.try_borrow_mut_data()?;
-            let mut cursor = std::io::Cursor::new(&mut data[..]);
-            parent_xft_account.serialize(&mut cursor)?;
+            parent_xft_account.try_serialize(&mut &mut data[..])?;
         }
-        // Transfer XFT from caller to receiver using anchor-spl
+        // Transfer XFT from caller to receiver using anchor-spl, works for SPL and Token-2022 mints.
+        // Restricted Token-2022 mints also need the transfer-hook accounts as remaining accounts.
         let transfer_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
+            token_interface::TransferChecked {
                 from: ctx.accounts.caller_token_account.to_account_info(),
-                to: ctx.accounts.receiver.to_account_info(),
+                mint: ctx.accounts.xft_mint.to_account_info(),
+                to: ctx.accounts.receiver_token_account.to_account_info(),
                 authority: ctx.accounts.caller.to_account_info(),
             },
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        transfer_checked_with_hook(transfer_ctx, settings.quantity, 0)?;
+        Ok(())
+    }
+
+    /// Lets the label owner pull a restricted item back from any holder through the mint's
+    /// permanent delegate (Token-2022 mints only). The transfer-hook accounts are passed as
+    /// remaining accounts.
+    pub fn recall_xft<'info>(ctx: Context<'_, '_, '_, 'info, RecallXft<'info>>, amount: u64) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.transfers, ErrorCode::TransfersPaused);
+        require_not_blocked(&ctx.accounts.label_owner_blocklist_entry, &ctx.accounts.label_owner.key())?;
+        let settings = ctx.accounts.xft_account.xft_settings()?;
+        require!(settings.transfer_rule == TransferRule::Restricted, ErrorCode::NotRecallable);
+        require!(
+            label_owned_by(&ctx.accounts.label_account, ctx.accounts.label_owner.key(), settings.label_id)?,
+            ErrorCode::Unauthorized
+        );
+        let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", &[ctx.bumps.mint_authority]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.holder_token_account.to_account_info(),
+                mint: ctx.accounts.xft_mint.to_account_info(),
+                to: ctx.accounts.label_owner_token_account.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        transfer_checked_with_hook(cpi_ctx, amount, 0)?;
+        emit!(XftRecalled {
+            xft_id: ctx.accounts.xft_account.xft_id,
+            holder: ctx.accounts.holder_token_account.owner,
+            label_owner: ctx.accounts.label_owner.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Burns `amount` editions held by the signer. Burning the last edition closes the
+    /// XftAccount, releases a label's title lookup so the title can be registered again and
+    /// closes an empty vault; their rent goes back to the owner (addresses[1]). Every token
+    /// account the vault owns is passed as a remaining account, xft-vault checks they are empty.
+    pub fn burn_xft<'info>(
+        ctx: Context<'_, '_, '_, 'info, BurnXft<'info>>,
+        xft_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.transfers, ErrorCode::TransfersPaused);
+        require!(amount > 0, ErrorCode::InvalidSettings);
+        let xft = load_xft_account(&ctx.accounts.xft_account, xft_id)?;
+        let settings = xft.xft_settings()?;
+        // Limited editions are burned one numbered edition at a time with burn_edition
+        require!(settings.quantity == 1, ErrorCode::LimitedEdition);
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: ctx.accounts.xft_mint.to_account_info(),
+                    from: ctx.accounts.holder_token_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        // Return the token account rent to the holder once it is empty
+        ctx.accounts.holder_token_account.reload()?;
+        if ctx.accounts.holder_token_account.amount == 0 {
+            token_interface::close_account(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::CloseAccount {
+                    account: ctx.accounts.holder_token_account.to_account_info(),
+                    destination: ctx.accounts.holder.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ))?;
+        }
+        ctx.accounts.xft_mint.reload()?;
+        let remaining_supply = ctx.accounts.xft_mint.supply;
+        let closed = remaining_supply == 0;
+        if closed {
+            let owner = ctx.accounts.owner.to_account_info();
+            require!(xft.addresses.get(1) == Some(&owner.key()), ErrorCode::Unauthorized);
+            require!(settings.children.iter().all(|child| *child == 0), ErrorCode::HasChildren);
+            if settings.label_type.is_label() {
+                let title_lookup = ctx.accounts.title_lookup.as_ref().ok_or(ErrorCode::InvalidTitleLookup)?;
+                require!(
+                    title_lookup.bump != 0 && title_lookup.xft_id == xft_id,
+                    ErrorCode::InvalidTitleLookup
+                );
+                title_lookup.close(owner.clone())?;
+            }
+            let vault_address = xft.addresses.get(2).copied().unwrap_or(Pubkey::default());
+            if vault_address != Pubkey::default() {
+                let vault = ctx.accounts.vault.as_ref().ok_or(ErrorCode::InvalidVault)?;
+                require!(vault.key() == vault_address, ErrorCode::InvalidVault);
+                let signer_seeds: &[&[&[u8]]] = &[&[b"vault_authority", &[ctx.bumps.vault_authority]]];
+                let cpi_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.vault_program.to_account_info(),
+                    CloseVault {
+                        vault: vault.to_account_info(),
+                        minter_authority: ctx.accounts.vault_authority.to_account_info(),
+                        receiver: owner.clone(),
+                    },
+                    signer_seeds,
+                )
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+                vault_cpi::close_vault(cpi_ctx, xft_id)?;
+            }
+            close_program_account(&ctx.accounts.xft_account, &owner)?;
+        }
+        emit!(XftBurned {
+            xft_id,
+            holder: ctx.accounts.holder.key(),
+            amount,
+            remaining_supply,
+            closed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Reverses wrap_xft: burns the full wrapped supply held by the signer, returns the parent
+    /// tokens from the wrap escrow, clears parent settings[11] and closes the wrapped vault
+    /// and XftAccount. Fails while anything is left in the wrapped vault. Restricted parents
+    /// need the transfer-hook accounts as remaining accounts, followed by the last
+    /// `vault_token_accounts` remaining accounts: every token account of the wrapped vault.
+    pub fn unwrap_xft<'info>(ctx: Context<'_, '_, '_, 'info, UnwrapXft<'info>>, vault_token_accounts: u8) -> Result<()> {
+        let split = ctx
+            .remaining_accounts
+            .len()
+            .checked_sub(vault_token_accounts as usize)
+            .ok_or(ErrorCode::InvalidVault)?;
+        let (hook_accounts, vault_token_accounts) = ctx.remaining_accounts.split_at(split);
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.minting, ErrorCode::MintingPaused);
+        let parent_xft_id = ctx.accounts.parent_xft.xft_id;
+        let wrapped_xft_id = ctx.accounts.wrapped_xft.xft_id;
+        let mut parent_settings = ctx.accounts.parent_xft.xft_settings()?;
+        require!(parent_settings.label_split == wrapped_xft_id, ErrorCode::NotWrapped);
+        let wrapped_quantity = ctx.accounts.wrapped_xft.xft_settings()?.quantity;
+        // Only the whole wrapped supply, in one hand, unwraps the parent
+        require!(ctx.accounts.wrapped_mint.supply == wrapped_quantity, ErrorCode::WrappedSupplyMismatch);
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: ctx.accounts.wrapped_mint.to_account_info(),
+                    from: ctx.accounts.holder_wrapped_token_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            wrapped_quantity,
+        )?;
+        // Give the parent tokens back from the wrap escrow
+        let signer_seeds: &[&[&[u8]]] = &[&[b"wrap_authority", &[ctx.bumps.wrap_authority]]];
+        transfer_checked_with_hook(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.wrap_escrow_token_account.to_account_info(),
+                    mint: ctx.accounts.parent_mint.to_account_info(),
+                    to: ctx.accounts.holder_parent_token_account.to_account_info(),
+                    authority: ctx.accounts.wrap_authority.to_account_info(),
+                },
+                signer_seeds,
+            )
+            .with_remaining_accounts(hook_accounts.to_vec()),
+            wrapped_quantity,
+            0,
+        )?;
+        parent_settings.label_split = 0;
+        ctx.accounts.parent_xft.set_xft_settings(&parent_settings);
+        // The wrapped XftAccount is closed below, its vault has to go with it. close_vault
+        // fails with VaultNotEmpty while anything is left in it.
+        let vault_address = ctx.accounts.wrapped_xft.addresses.get(2).copied().unwrap_or(Pubkey::default());
+        if vault_address != Pubkey::default() {
+            let vault = ctx.accounts.vault.as_ref().ok_or(ErrorCode::InvalidVault)?;
+            require!(vault.key() == vault_address, ErrorCode::InvalidVault);
+            let signer_seeds: &[&[&[u8]]] = &[&[b"vault_authority", &[ctx.bumps.vault_authority]]];
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.vault_program.to_account_info(),
+                CloseVault {
+                    vault: vault.to_account_info(),
+                    minter_authority: ctx.accounts.vault_authority.to_account_info(),
+                    receiver: ctx.accounts.holder.to_account_info(),
+                },
+                signer_seeds,
+            )
+            .with_remaining_accounts(vault_token_accounts.to_vec());
+            vault_cpi::close_vault(cpi_ctx, wrapped_xft_id)?;
+        }
+        emit!(UnwrappedXftEvent {
+            parent_xft_id,
+            wrapped_xft_id,
+            wrapper: ctx.accounts.holder.key(),
+            vault: vault_address,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Deposits an external SPL or Metaplex NFT into the wrap escrow and mints a type 8 (wrapped)
+    /// 1 of 1 XFT with its own vault to the depositor. The original mint and the Metaplex
+    /// metadata URI, when a metadata account is passed, are kept in WrappedNft until redeem_nft.
+    pub fn wrap_nft(ctx: Context<WrapNft>) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.minting, ErrorCode::MintingPaused);
+        require_not_blocked(&ctx.accounts.depositor_blocklist_entry, &ctx.accounts.depositor.key())?;
+        let nft_mint = &ctx.accounts.nft_mint;
+        // A fixed supply of one, nobody can mint a second token
+        require!(
+            nft_mint.decimals == 0 && nft_mint.supply == 1 && nft_mint.mint_authority.is_none(),
+            ErrorCode::InvalidNft
+        );
+        let (name, metadata_uri) = match ctx.accounts.nft_metadata.as_ref() {
+            Some(metadata) => metaplex_metadata(metadata, &nft_mint.key())?,
+            None => (String::new(), String::new()),
+        };
+        require!(metadata_uri.len() <= MAX_METADATA_URI_LEN, ErrorCode::MetadataUriTooLong);
+        // Move the NFT into the escrow owned by the wrap authority
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.nft_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.depositor_nft_token_account.to_account_info(),
+                    mint: nft_mint.to_account_info(),
+                    to: ctx.accounts.nft_escrow.to_account_info(),
+                    authority: ctx.accounts.depositor.to_account_info(),
+                },
+            ),
+            1,
+            0,
+        )?;
+
+        let xft_id = ctx.accounts.counter.value;
+        ctx.accounts.counter.value += 1;
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.vault_program.to_account_info(),
+            CreateVault {
+                vault: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.depositor.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                minter_program: ctx.accounts.vault_program.to_account_info(),
+            },
+        );
+        vault_cpi::create_vault(cpi_ctx, xft_id, LabelType::WrappedTo.as_u64())?;
+
+        // Unlinked, transferable 1 of 1 of type 8 (settings[3], [6] and [9])
+        let settings = XftSettings::new(&[
+            0,
+            0,
+            0,
+            LabelType::WrappedTo.as_u64(),
+            0,
+            0,
+            1,
+            0,
+            0,
+            TransferRule::Transferable.as_u64(),
+        ])?;
+        let xft_account = &mut ctx.accounts.xft;
+        xft_account.xft_id = xft_id;
+        xft_account.settings = settings.encode();
+        xft_account.addresses = vec![
+            ctx.accounts.depositor.key(),
+            ctx.accounts.depositor.key(),
+            ctx.accounts.vault.key(),
+        ];
+        xft_account.bump = ctx.bumps.xft;
+        xft_account.ipfs = metadata_uri.clone();
+
+        let wrapped_nft = &mut ctx.accounts.wrapped_nft;
+        wrapped_nft.xft_id = xft_id;
+        wrapped_nft.original_mint = nft_mint.key();
+        wrapped_nft.metadata_uri = metadata_uri.clone();
+        wrapped_nft.depositor = ctx.accounts.depositor.key();
+        wrapped_nft.bump = ctx.bumps.wrapped_nft;
+
+        create_xft_mint(
+            &XftMintAccounts {
+                payer: ctx.accounts.depositor.to_account_info(),
+                xft_mint: ctx.accounts.xft_mint.to_account_info(),
+                mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            &[b"xft_mint", xft_id.to_le_bytes().as_ref(), &[ctx.bumps.xft_mint]],
+            ctx.bumps.mint_authority,
+            &name,
+            &metadata_uri,
+            settings.transfer_rule,
+        )?;
+        let expected_token_account = get_associated_token_address_with_program_id(
+            &ctx.accounts.depositor.key(),
+            &ctx.accounts.xft_mint.key(),
+            &ctx.accounts.token_program.key(),
+        );
+        require!(ctx.accounts.depositor_xft_token_account.key() == expected_token_account, ErrorCode::InvalidTokenAccount);
+        associated_token::create(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.depositor.to_account_info(),
+                associated_token: ctx.accounts.depositor_xft_token_account.to_account_info(),
+                authority: ctx.accounts.depositor.to_account_info(),
+                mint: ctx.accounts.xft_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+        // The wrapped XFT is a 1 of 1, mint it and drop the mint authority
+        let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", &[ctx.bumps.mint_authority]]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.xft_mint.to_account_info(),
+                    to: ctx.accounts.depositor_xft_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            1,
+        )?;
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::SetAuthority {
+                    current_authority: ctx.accounts.mint_authority.to_account_info(),
+                    account_or_mint: ctx.accounts.xft_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        emit!(NftWrapped {
+            xft_id,
+            original_mint: nft_mint.key(),
+            metadata_uri,
+            depositor: ctx.accounts.depositor.key(),
+            vault: ctx.accounts.vault.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Burns a wrap_nft XFT and releases the original NFT from the wrap escrow to the holder.
+    /// The vault has to be emptied first, it is closed together with the XftAccount, the
+    /// WrappedNft record and the escrow token account. Every token account the vault owns is
+    /// passed as a remaining account.
+    pub fn redeem_nft<'info>(ctx: Context<'_, '_, '_, 'info, RedeemNft<'info>>, xft_id: u64) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.minting, ErrorCode::MintingPaused);
+        require_not_blocked(&ctx.accounts.holder_blocklist_entry, &ctx.accounts.holder.key())?;
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: ctx.accounts.xft_mint.to_account_info(),
+                    from: ctx.accounts.holder_xft_token_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+        // Release the NFT and close the escrow, signed by the wrap authority
+        let signer_seeds: &[&[&[u8]]] = &[&[b"wrap_authority", &[ctx.bumps.wrap_authority]]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.nft_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.nft_escrow.to_account_info(),
+                    mint: ctx.accounts.nft_mint.to_account_info(),
+                    to: ctx.accounts.holder_nft_token_account.to_account_info(),
+                    authority: ctx.accounts.wrap_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            1,
+            0,
+        )?;
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.nft_token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: ctx.accounts.nft_escrow.to_account_info(),
+                destination: ctx.accounts.holder.to_account_info(),
+                authority: ctx.accounts.wrap_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+        // Fails with VaultNotEmpty while anything is left in the vault
+        let signer_seeds: &[&[&[u8]]] = &[&[b"vault_authority", &[ctx.bumps.vault_authority]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.vault_program.to_account_info(),
+            CloseVault {
+                vault: ctx.accounts.vault.to_account_info(),
+                minter_authority: ctx.accounts.vault_authority.to_account_info(),
+                receiver: ctx.accounts.holder.to_account_info(),
+            },
+            signer_seeds,
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        vault_cpi::close_vault(cpi_ctx, xft_id)?;
+
+        emit!(NftRedeemed {
+            xft_id,
+            original_mint: ctx.accounts.wrapped_nft.original_mint,
+            holder: ctx.accounts.holder.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Prints the next numbered edition "n of N" of a limited edition XFT to `recipient`. The
+    /// master XftAccount keeps N (settings[6]); its creator or owner prints, optionally giving
+    /// the edition its own vault. Every edition is its own single token mint, so whoever holds
+    /// that token owns the edition.
+    pub fn print_edition(ctx: Context<PrintEdition>, xft_id: u64, with_vault: bool) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.minting, ErrorCode::MintingPaused);
+        require_not_blocked(&ctx.accounts.authority_blocklist_entry, &ctx.accounts.authority.key())?;
+        require_not_blocked(&ctx.accounts.recipient_blocklist_entry, &ctx.accounts.recipient.key())?;
+        let xft_account = &ctx.accounts.xft_account;
+        let settings = xft_account.xft_settings()?;
+        require!(settings.quantity > 1, ErrorCode::NotLimitedEdition);
+        let authority = ctx.accounts.authority.key();
+        require!(
+            xft_account.addresses.first() == Some(&authority) || xft_account.addresses.get(1) == Some(&authority),
+            ErrorCode::Unauthorized
+        );
+        let ipfs = xft_account.ipfs.clone();
+        let master = &mut ctx.accounts.master_edition;
+        if master.max_supply == 0 {
+            master.xft_id = xft_id;
+            master.max_supply = settings.quantity;
+            master.bump = ctx.bumps.master_edition;
+        }
+        require!(master.printed < master.max_supply, ErrorCode::EditionSupplyExhausted);
+        master.printed += 1;
+        let number = master.printed;
+        let max_supply = master.max_supply;
+        create_xft_mint(
+            &XftMintAccounts {
+                payer: ctx.accounts.authority.to_account_info(),
+                xft_mint: ctx.accounts.edition_mint.to_account_info(),
+                mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            &[
+                b"edition_mint",
+                xft_id.to_le_bytes().as_ref(),
+                number.to_le_bytes().as_ref(),
+                &[ctx.bumps.edition_mint],
+            ],
+            ctx.bumps.mint_authority,
+            &format!("Edition {} of {}", number, max_supply),
+            &ipfs,
+            settings.transfer_rule,
+        )?;
+        init_transfer_hook_metas(
+            TransferHookAccounts {
+                payer: ctx.accounts.authority.to_account_info(),
+                extra_account_meta_list: ctx.accounts.extra_account_meta_list.as_ref().map(|a| a.to_account_info()),
+                mint: ctx.accounts.edition_mint.to_account_info(),
+                xft_account: ctx.accounts.xft_account.to_account_info(),
+                label_account: ctx.accounts.label_account.clone(),
+                transfer_hook_program: ctx.accounts.transfer_hook_program.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            settings.transfer_rule,
+            xft_id,
+            number,
+        )?;
+        let expected_token_account = get_associated_token_address_with_program_id(
+            &ctx.accounts.recipient.key(),
+            &ctx.accounts.edition_mint.key(),
+            &ctx.accounts.token_program.key(),
+        );
+        require!(ctx.accounts.recipient_token_account.key() == expected_token_account, ErrorCode::InvalidTokenAccount);
+        associated_token::create(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.authority.to_account_info(),
+                associated_token: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: ctx.accounts.recipient.to_account_info(),
+                mint: ctx.accounts.edition_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+        // One token, then the edition mint is closed for good
+        let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", &[ctx.bumps.mint_authority]]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::MintTo {
+                    mint: ctx.accounts.edition_mint.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            1,
+        )?;
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::SetAuthority {
+                    current_authority: ctx.accounts.mint_authority.to_account_info(),
+                    account_or_mint: ctx.accounts.edition_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+        let mut vault_address = Pubkey::default();
+        if with_vault {
+            let vault = ctx.accounts.vault.as_ref().ok_or(ErrorCode::InvalidVault)?;
+            let signer_seeds: &[&[&[u8]]] = &[&[b"vault_authority", &[ctx.bumps.vault_authority]]];
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.vault_program.to_account_info(),
+                CreateEditionVault {
+                    vault: vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                    minter_authority: ctx.accounts.vault_authority.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                signer_seeds,
+            );
+            vault_cpi::create_edition_vault(cpi_ctx, xft_id, number, settings.label_type.as_u64())?;
+            vault_address = vault.key();
+        }
+        let edition = &mut ctx.accounts.edition;
+        edition.xft_id = xft_id;
+        edition.number = number;
+        edition.max_supply = max_supply;
+        edition.mint = ctx.accounts.edition_mint.key();
+        edition.vault = vault_address;
+        edition.bump = ctx.bumps.edition;
+        emit!(EditionPrinted {
+            xft_id,
+            edition: number,
+            max_supply,
+            mint: edition.mint,
+            owner: ctx.accounts.recipient.key(),
+            vault: vault_address,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Sends numbered edition `number` of xft_id to `receiver` with the pause, blocklist and
+    /// transfer rule checks of transfer_xft. The edition token itself carries the ownership.
+    pub fn transfer_edition<'info>(
+        ctx: Context<'_, '_, '_, 'info, TransferEdition<'info>>,
+        xft_id: u64,
+        number: u64,
+    ) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.transfers, ErrorCode::TransfersPaused);
+        require_not_blocked(&ctx.accounts.owner_blocklist_entry, &ctx.accounts.owner.key())?;
+        require_not_blocked(&ctx.accounts.receiver_blocklist_entry, &ctx.accounts.receiver.key())?;
+        let settings = ctx.accounts.xft_account.xft_settings()?;
+        require_can_send(
+            &settings,
+            &ctx.accounts.label_account,
+            ctx.accounts.operator_account.as_ref(),
+            ctx.accounts.owner.key(),
+        )?;
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.owner_token_account.to_account_info(),
+                mint: ctx.accounts.edition_mint.to_account_info(),
+                to: ctx.accounts.receiver_token_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        transfer_checked_with_hook(transfer_ctx, 1, 0)?;
+        emit!(EditionTransferred {
+            xft_id,
+            edition: number,
+            from: ctx.accounts.owner.key(),
+            to: ctx.accounts.receiver.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Burns numbered edition `number` of xft_id held by the signer and closes its edition record
+    /// and edition vault, their rent goes to the owner (addresses[1]). Once every edition of the
+    /// run has been printed and burned, the master XftAccount, its print state and a label's
+    /// title lookup are closed as well. Every token account the edition vault owns is passed as
+    /// a remaining account, xft-vault checks they are empty.
+    pub fn burn_edition<'info>(
+        ctx: Context<'_, '_, '_, 'info, BurnEdition<'info>>,
+        xft_id: u64,
+        number: u64,
+    ) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.transfers, ErrorCode::TransfersPaused);
+        let xft = load_xft_account(&ctx.accounts.xft_account, xft_id)?;
+        let settings = xft.xft_settings()?;
+        let owner = ctx.accounts.owner.to_account_info();
+        require!(xft.addresses.get(1) == Some(&owner.key()), ErrorCode::Unauthorized);
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::Burn {
+                    mint: ctx.accounts.edition_mint.to_account_info(),
+                    from: ctx.accounts.holder_token_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+        // Every edition is a single token, so the holder's token account is empty now
+        token_interface::close_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: ctx.accounts.holder_token_account.to_account_info(),
+                destination: ctx.accounts.holder.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ))?;
+        let vault_address = ctx.accounts.edition.vault;
+        if vault_address != Pubkey::default() {
+            let vault = ctx.accounts.vault.as_ref().ok_or(ErrorCode::InvalidVault)?;
+            require!(vault.key() == vault_address, ErrorCode::InvalidVault);
+            let signer_seeds: &[&[&[u8]]] = &[&[b"vault_authority", &[ctx.bumps.vault_authority]]];
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.vault_program.to_account_info(),
+                CloseEditionVault {
+                    vault: vault.to_account_info(),
+                    minter_authority: ctx.accounts.vault_authority.to_account_info(),
+                    receiver: owner.clone(),
+                },
+                signer_seeds,
+            )
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+            vault_cpi::close_edition_vault(cpi_ctx, xft_id, number)?;
+        }
+        let master = &mut ctx.accounts.master_edition;
+        master.burned += 1;
+        let closed = master.printed == master.max_supply && master.burned == master.printed;
+        if closed {
+            require!(settings.children.iter().all(|child| *child == 0), ErrorCode::HasChildren);
+            if settings.label_type.is_label() {
+                let title_lookup = ctx.accounts.title_lookup.as_ref().ok_or(ErrorCode::InvalidTitleLookup)?;
+                require!(
+                    title_lookup.bump != 0 && title_lookup.xft_id == xft_id,
+                    ErrorCode::InvalidTitleLookup
+                );
+                title_lookup.close(owner.clone())?;
+            }
+            ctx.accounts.master_edition.close(owner.clone())?;
+            close_program_account(&ctx.accounts.xft_account, &owner)?;
+        }
+        emit!(EditionBurned {
+            xft_id,
+            edition: number,
+            holder: ctx.accounts.holder.key(),
+            closed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Points xft_id at a new metadata URI. The label owner or an active operator of the label
+    /// (the XFT itself for labels) can update until the metadata is frozen; the previous URI
+    /// goes into the bounded MetadataHistory and Token-2022 mints get their metadata URI updated.
+    pub fn update_metadata(ctx: Context<UpdateMetadata>, xft_id: u64, ipfs: String) -> Result<()> {
+        require!(!ipfs.is_empty() && ipfs.len() <= MAX_METADATA_URI_LEN, ErrorCode::MetadataUriTooLong);
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.minting, ErrorCode::MintingPaused);
+        require_metadata_authority(
+            &ctx.accounts.xft_account,
+            &ctx.accounts.label_account,
+            ctx.accounts.operator_account.as_ref(),
+            ctx.accounts.authority.key(),
+        )?;
+        let history = &mut ctx.accounts.metadata_history;
+        require!(!history.frozen, ErrorCode::MetadataFrozen);
+        history.xft_id = xft_id;
+        history.bump = ctx.bumps.metadata_history;
+        let previous_ipfs = std::mem::replace(&mut ctx.accounts.xft_account.ipfs, ipfs.clone());
+        history.previous_uris.push(previous_ipfs.clone());
+        if history.previous_uris.len() > MAX_METADATA_HISTORY {
+            history.previous_uris.remove(0);
+        }
+        history.version += 1;
+        // The XftAccount is not reallocated, the new URI has to fit its space
+        let serialized_len = 8 + ctx.accounts.xft_account.try_to_vec()?.len();
+        require!(
+            serialized_len <= ctx.accounts.xft_account.to_account_info().data_len(),
+            ErrorCode::MetadataUriTooLong
         );
-        token::transfer(transfer_ctx, settings[6])?;
+        if ctx.accounts.token_program.key() == Token2022::id() {
+            // Token-2022 reallocates the metadata itself, the mint has to cover the extra rent
+            let mint_info = ctx.accounts.xft_mint.to_account_info();
+            let growth = ipfs.len().saturating_sub(previous_ipfs.len());
+            let required = Rent::get()?.minimum_balance(mint_info.data_len() + growth);
+            let top_up = required.saturating_sub(mint_info.lamports());
+            if top_up > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: ctx.accounts.authority.to_account_info(),
+                            to: mint_info.clone(),
+                        },
+                    ),
+                    top_up,
+                )?;
+            }
+            let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", &[ctx.bumps.mint_authority]]];
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TokenMetadataUpdateField {
+                        program_id: ctx.accounts.token_program.to_account_info(),
+                        metadata: mint_info,
+                        update_authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                Field::Uri,
+                ipfs.clone(),
+            )?;
+        }
+        emit!(MetadataUpdated {
+            xft_id,
+            version: history.version,
+            previous_ipfs,
+            ipfs,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Freezes the metadata of xft_id for good, update_metadata fails from then on
+    pub fn freeze_metadata(ctx: Context<FreezeMetadata>, xft_id: u64) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.minting, ErrorCode::MintingPaused);
+        require_metadata_authority(
+            &ctx.accounts.xft_account,
+            &ctx.accounts.label_account,
+            ctx.accounts.operator_account.as_ref(),
+            ctx.accounts.authority.key(),
+        )?;
+        let history = &mut ctx.accounts.metadata_history;
+        require!(!history.frozen, ErrorCode::MetadataFrozen);
+        history.xft_id = xft_id;
+        history.bump = ctx.bumps.metadata_history;
+        history.frozen = true;
+        emit!(MetadataFrozen {
+            xft_id,
+            version: history.version,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
@@ -339,23 +1331,182 @@ pub mod minter {
             ErrorCode::Unauthorized
         );
 
-        // Update the XFT account settings
+        // Lock the label vault until unlock_date
         let xft_account = &mut ctx.accounts.xft_account;
-        let mut settings = xft_account.settings.clone();
+        let mut settings = xft_account.xft_settings()?;
+        settings.vault_locked = true;
+        settings.vault_unlock_date = unlock_date;
+        xft_account.set_xft_settings(&settings);
         
-        // Ensure settings vector has enough elements
-        while settings.len() <= 13 {
-            settings.push(0);
+        Ok(())
+    }
+    /// Extends a label registration by `years`. Anyone can pay, so renewals can be gifted.
+    /// `title` is the label's title, renewals are priced like mint_xft.
+    pub fn renew_label(ctx: Context<RenewLabel>, xft_id: u64, title: String, years: u64) -> Result<()> {
+        require!(years > 0, ErrorCode::InvalidSettings);
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.minting, ErrorCode::MintingPaused);
+        require!(ctx.accounts.title_lookup.xft_id == xft_id, ErrorCode::InvalidTitleLookup);
+        let mut label = load_xft_account(&ctx.accounts.label_account, xft_id)?;
+        require_not_blocked(&ctx.accounts.payer_blocklist_entry, &ctx.accounts.payer.key())?;
+        require_not_blocked(&ctx.accounts.label_owner_blocklist_entry, &label.addresses[1])?;
+        let mut settings = label.xft_settings()?;
+        require!(settings.label_type.is_label(), ErrorCode::InvalidXftType);
+        let now = Clock::get()?.unix_timestamp;
+        // Expired lead and profile labels can only be renewed by their owner during the grace
+        // period, after that they go to the reclaim auction
+        if settings.label_type.is_reclaimable() && settings.is_expired(now as u64) {
+            require!(settings.in_grace_period(now as u64), ErrorCode::GracePeriodOver);
+            require!(ctx.accounts.payer.key() == label.addresses[1], ErrorCode::GracePeriodOwnerOnly);
         }
-        
-        // Update settings[12] = 1 and settings[13] = unlock_date
-        settings[12] = 1;
-        settings[13] = unlock_date;
-        
-        xft_account.settings = settings;
-        
+        // Renewals cost what minting the label for `years` would: the title's premium when it
+        // is reserved with one, the xft-admin fee schedule price otherwise
+        let normalized_title = normalize_title(&title)?;
+        let total_fee = match TitleReservation::load(&ctx.accounts.title_reservation, &normalized_title)? {
+            Some(reservation) if reservation.premium_fee_per_year > 0 => {
+                reservation.premium_fee_per_year.checked_mul(years).ok_or(ErrorCode::InvalidSettings)?
+            }
+            _ => {
+                require_fee_schedule(&ctx.accounts.fee_schedule, settings.label_type)?;
+                let cpi_ctx = CpiContext::new(
+                    ctx.accounts.admin_program.to_account_info(),
+                    admin_xft_cpi::GetMintFee {
+                        admin: ctx.accounts.admin_account.to_account_info(),
+                        fee_schedule: ctx.accounts.fee_schedule.to_account_info(),
+                    },
+                );
+                admin_xft_cpi::cpi::get_mint_fee(
+                    cpi_ctx,
+                    settings.label_type.as_u64(),
+                    normalized_title.chars().count() as u64,
+                    years,
+                )?
+            }
+        };
+        let fee_paid = pay_protocol_fee(
+            &ctx.accounts.admin_program,
+            &ctx.accounts.treasury,
+            &ctx.accounts.treasury_token_account,
+            &ctx.accounts.payer,
+            &ctx.accounts.payment_mint,
+            &ctx.accounts.payer_payment_token_account,
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.system_program,
+            total_fee,
+        )?;
+        let previous_expire = settings.registration_expire;
+        settings.renew(now as u64, years);
+        label.set_xft_settings(&settings);
+        let mut data = ctx.accounts.label_account.try_borrow_mut_data()?;
+        label.try_serialize(&mut &mut data[..])?;
+        emit!(LabelRenewed {
+            xft_id,
+            payer: ctx.accounts.payer.key(),
+            years,
+            fee_paid,
+            previous_expire,
+            registration_expire: settings.registration_expire,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Gives an expired lead or profile label to the winner of the xft-market reclaim auction.
+    /// The winner takes over the XftAccount (and with it the title_lookup entry pointing at
+    /// xft_id) and the vault, whose SOL stays claimable by the previous owner. SOL an earlier
+    /// owner never claimed is paid out to them (previous_claimant) during the handover.
+    pub fn reclaim_label(ctx: Context<ReclaimLabel>, xft_id: u64, new_owner: Pubkey, years: u64) -> Result<()> {
+        let market_program_id = Pubkey::from_str(MARKET_PROGRAM_ID).unwrap();
+        let (market_authority, _) = Pubkey::find_program_address(&[b"reclaim_authority"], &market_program_id);
+        require!(ctx.accounts.market_authority.key() == market_authority, ErrorCode::Unauthorized);
+        require!(years > 0, ErrorCode::InvalidSettings);
+        let mut label = load_xft_account(&ctx.accounts.label_account, xft_id)?;
+        let mut settings = label.xft_settings()?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(settings.is_reclaimable(now as u64), ErrorCode::LabelNotReclaimable);
+        let previous_owner = label.addresses[1];
+        // Fresh registration for the new owner, the previous vault lock does not carry over
+        settings.registration_years = years;
+        settings.start_registration(now as u64);
+        settings.vault_locked = false;
+        settings.vault_unlock_date = 0;
+        label.set_xft_settings(&settings);
+        label.addresses[1] = new_owner;
+        let vault_address = label.addresses.get(2).copied().unwrap_or(Pubkey::default());
+        if vault_address != Pubkey::default() {
+            let vault = ctx.accounts.vault.as_ref().ok_or(ErrorCode::InvalidVault)?;
+            require!(vault.key() == vault_address, ErrorCode::InvalidVault);
+            let signer_seeds: &[&[&[u8]]] = &[&[b"vault_authority", &[ctx.bumps.vault_authority]]];
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.vault_program.to_account_info(),
+                HandOverVault {
+                    vault: vault.to_account_info(),
+                    minter_authority: ctx.accounts.vault_authority.to_account_info(),
+                    previous_claimant: ctx.accounts.previous_claimant.clone(),
+                },
+                signer_seeds,
+            );
+            vault_cpi::hand_over_vault(cpi_ctx, xft_id, previous_owner)?;
+        }
+        let mut data = ctx.accounts.label_account.try_borrow_mut_data()?;
+        label.try_serialize(&mut &mut data[..])?;
+        emit!(LabelReclaimed {
+            xft_id,
+            previous_owner,
+            new_owner,
+            registration_expire: settings.registration_expire,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
+    /// Lists `child_id` in the children of label `xft_id` while it is for sale under the label.
+    /// Only xft-market can call this, signing with its escrow authority PDA.
+    pub fn add_label_child(ctx: Context<UpdateLabelChildren>, xft_id: u64, child_id: u64) -> Result<()> {
+        require_market_escrow_authority(&ctx.accounts.market_authority)?;
+        let mut label = load_xft_account(&ctx.accounts.label_account, xft_id)?;
+        let mut settings = label.xft_settings()?;
+        settings.add_child(child_id);
+        label.set_xft_settings(&settings);
+        let mut data = ctx.accounts.label_account.try_borrow_mut_data()?;
+        label.try_serialize(&mut &mut data[..])?;
+        Ok(())
+    }
+
+    /// Removes `child_id` from the children of label `xft_id` once its listing is sold out or
+    /// cancelled. Only xft-market can call this, signing with its escrow authority PDA.
+    pub fn remove_label_child(ctx: Context<UpdateLabelChildren>, xft_id: u64, child_id: u64) -> Result<()> {
+        require_market_escrow_authority(&ctx.accounts.market_authority)?;
+        let mut label = load_xft_account(&ctx.accounts.label_account, xft_id)?;
+        let mut settings = label.xft_settings()?;
+        require!(settings.remove_child(child_id), ErrorCode::NotLabelChild);
+        label.set_xft_settings(&settings);
+        let mut data = ctx.accounts.label_account.try_borrow_mut_data()?;
+        label.try_serialize(&mut &mut data[..])?;
+        Ok(())
+    }
+
+    /// Returns true when `address` owns the unexpired label `xft_id`
+    pub fn is_label_owner(ctx: Context<IsLabelOwner>, address: Pubkey, xft_id: u64) -> Result<bool> {
+        label_owned_by(&ctx.accounts.label_account, address, xft_id)
+    }
+
+    /// Moves a title lookup created before title normalization, seeded by the raw title bytes,
+    /// to the normalized title_seed PDA so the title cannot be registered again in another
+    /// case or spelling. Permissionless, the caller pays the new lookup and gets the old rent.
+    pub fn migrate_title_lookup(ctx: Context<MigrateTitleLookup>, legacy_title: String) -> Result<()> {
+        require!(
+            legacy_title.as_bytes() != title_seed(&legacy_title).as_slice(),
+            ErrorCode::TitleAlreadyMigrated
+        );
+        // A case variant of an already claimed title keeps its legacy lookup
+        require!(ctx.accounts.title_lookup.bump == 0, ErrorCode::TitleAlreadyExists);
+        let title_lookup = &mut ctx.accounts.title_lookup;
+        title_lookup.xft_id = ctx.accounts.legacy_title_lookup.xft_id;
+        title_lookup.bump = ctx.bumps.title_lookup;
         Ok(())
     }
+
     pub fn initialize_counter(ctx: Context<InitializeCounter>) -> Result<()> {
         let counter = &mut ctx.accounts.counter;
         counter.value = 0;
@@ -373,79 +1524,558 @@ fn check_operator_permission(ctx: &Context<MintXft>, label_id: u64) -> Result<()
     Ok(())
 }
 
-pub fn is_label_owner(accounts: &MintXft, address: Pubkey, xft_id: u64) -> Result<bool> {
-    // TODO: Replace with actual account fetch logic
-    let settings: Vec<u64> = vec![0; 8]; // placeholder
-    let addresses: Vec<Pubkey> = vec![Pubkey::default(); 2]; // placeholder
-    let xft_type = settings.get(3).copied().unwrap_or(0);
-    if xft_type == 7 || xft_type == 8 {
+// Soulbound XFTs never move, restricted XFTs can only be sent by the label creator, the label
+// owner or an active operator of the label
+fn require_can_send(
+    settings: &XftSettings,
+    label_account: &AccountInfo,
+    operator_account: Option<&AccountInfo>,
+    sender: Pubkey,
+) -> Result<()> {
+    match settings.transfer_rule {
+        TransferRule::Soulbound => err!(ErrorCode::NotTransferable),
+        TransferRule::Transferable => Ok(()),
+        TransferRule::Restricted => {
+            let label = load_xft_account(label_account, settings.label_id)?;
+            if label.addresses.first() == Some(&sender) || label.addresses.get(1) == Some(&sender) {
+                return Ok(());
+            }
+            let operator_account = operator_account.ok_or(ErrorCode::Unauthorized)?;
+            require!(
+                active_operator_role(operator_account, &sender, settings.label_id, Clock::get()?.unix_timestamp as u64).is_some(),
+                ErrorCode::Unauthorized
+            );
+            Ok(())
+        }
+    }
+}
+
+// Metadata of an XFT is managed by the owner or an active operator of its label; labels are
+// their own label and unlinked items are managed by their owner (addresses[1])
+fn require_metadata_authority(
+    xft_account: &XftAccount,
+    label_account: &AccountInfo,
+    operator_account: Option<&AccountInfo>,
+    authority: Pubkey,
+) -> Result<()> {
+    let settings = xft_account.xft_settings()?;
+    let label_id = if settings.label_type.is_label() { xft_account.xft_id } else { settings.label_id };
+    if label_id == 0 {
+        require!(xft_account.addresses.get(1) == Some(&authority), ErrorCode::Unauthorized);
+        return Ok(());
+    }
+    if label_owned_by(label_account, authority, label_id)? {
+        return Ok(());
+    }
+    let operator_account = operator_account.ok_or(ErrorCode::Unauthorized)?;
+    require!(
+        active_operator_role(operator_account, &authority, label_id, Clock::get()?.unix_timestamp as u64).is_some(),
+        ErrorCode::Unauthorized
+    );
+    Ok(())
+}
+
+pub fn label_owned_by(label_account: &AccountInfo, address: Pubkey, xft_id: u64) -> Result<bool> {
+    let xft_account = load_xft_account(label_account, xft_id)?;
+    let settings = xft_account.xft_settings()?;
+    if !settings.label_type.is_label() {
         return Ok(false);
     }
-    let label_owner = addresses.get(1).copied().unwrap_or(Pubkey::default());
+    let label_owner = xft_account.addresses.get(1).copied().unwrap_or(Pubkey::default());
     if address != label_owner {
         return Ok(false);
     }
-    let expire = settings.get(7).copied().unwrap_or(0);
     let now = Clock::get()?.unix_timestamp as u64;
-    if expire <= now {
+    if settings.is_expired(now) {
         return Ok(false);
     }
     Ok(true)
 }
 
-pub fn is_market_license(xft_account_info: &AccountInfo, parent_xft_account_info: &AccountInfo) -> Result<(bool, u64, Pubkey, u64)> {
-    let xft_account = XftAccount::try_from_slice(&xft_account_info.data.borrow())?;
-    let settings = xft_account.settings;
-    // Check if settings[7] > now (expire check)
-    let expire = settings.get(7).copied().unwrap_or(0);
-    let now = Clock::get()?.unix_timestamp as u64;
-    if expire <= now {
-        return Ok((false, 0, Pubkey::default(), 0));
-    }
-    // Get the parent xft account (settings[0] of xft_id)
-    let parent_xft_id = settings.get(0).copied().unwrap_or(0);
-    if parent_xft_id == 0 {
-        return Ok((false, 0, Pubkey::default(), 0));
-    }
-    let parent_xft_account = XftAccount::try_from_slice(&parent_xft_account_info.data.borrow())?;
-    let parent_settings = parent_xft_account.settings;
-    let parent_addresses = parent_xft_account.addresses;
-    // Check if settings[7] of parent_xft account > now
-    let parent_expire = parent_settings.get(7).copied().unwrap_or(0);
-    if parent_expire <= now {
-        return Ok((false, parent_xft_id, Pubkey::default(), 0));
-    }
-    // Check parent_addresses[2] and parent_settings[8]
-    let parent_vault = parent_addresses.get(2).copied().unwrap_or(Pubkey::default());
-    let parent_setting_8 = parent_settings.get(8).copied().unwrap_or(0);
-    Ok((true, parent_xft_id, parent_vault, parent_setting_8))
+// Accounts xft-transfer-hook::initialize_extra_account_meta_list needs, the optional ones are
+// only passed for restricted items
+struct TransferHookAccounts<'info> {
+    payer: AccountInfo<'info>,
+    extra_account_meta_list: Option<AccountInfo<'info>>,
+    mint: AccountInfo<'info>,
+    xft_account: AccountInfo<'info>,
+    label_account: Option<AccountInfo<'info>>,
+    transfer_hook_program: Option<AccountInfo<'info>>,
+    system_program: AccountInfo<'info>,
 }
 
-pub fn get_vault(xft_account_info: &AccountInfo) -> Result<Pubkey> {
-    let xft_account = XftAccount::try_from_slice(&xft_account_info.data.borrow())?;
-    let addresses = xft_account.addresses;
-    let vault = addresses.get(2).copied().unwrap_or(Pubkey::default());
-    Ok(vault)
+// Writes the extra-account-meta list of a restricted mint, without it Token-2022 cannot
+// resolve the accounts xft-transfer-hook reads on every transfer. Other rules have no hook.
+// The XftAccount has to be written before, the hook reads its settings.
+fn init_transfer_hook_metas(
+    accounts: TransferHookAccounts,
+    transfer_rule: TransferRule,
+    xft_id: u64,
+    edition: u64,
+) -> Result<()> {
+    if transfer_rule != TransferRule::Restricted {
+        return Ok(());
+    }
+    let missing = || error!(ErrorCode::MissingTransferHookAccounts);
+    let cpi_ctx = CpiContext::new(
+        accounts.transfer_hook_program.ok_or_else(missing)?,
+        xft_transfer_hook::cpi::accounts::InitializeExtraAccountMetaList {
+            payer: accounts.payer,
+            extra_account_meta_list: accounts.extra_account_meta_list.ok_or_else(missing)?,
+            mint: accounts.mint,
+            xft_account: accounts.xft_account,
+            label_account: accounts.label_account.ok_or_else(missing)?,
+            system_program: accounts.system_program,
+        },
+    );
+    xft_transfer_hook::cpi::initialize_extra_account_meta_list(cpi_ctx, xft_id, edition)
 }
 
+// Accounts create_xft_mint needs, shared by mint_xft and wrap_nft
+struct XftMintAccounts<'info> {
+    payer: AccountInfo<'info>,
+    xft_mint: AccountInfo<'info>,
+    mint_authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+}
 
-// Account structs for Anchor instructions
-
-#[account]
-pub struct XftAccount {
-    pub xft_id: u64,
+// Creates the 0 decimals SPL mint at its PDA, `mint_seeds` are its signer seeds with bump:
+// [b"xft_mint", xft_id] for XFTs and [b"edition_mint", xft_id, number] for printed editions.
+// Passing the Token-2022 program makes a Token-2022 mint that carries the title and ipfs URI
+// in the metadata extension; soulbound items are non-transferable and restricted items get
+// the mint authority PDA as permanent delegate so label owners can recall them with
+// recall_xft; both rules are only enforced by Token-2022, so the classic token program is
+// refused for them. Restricted items also point their transfer hook at xft-transfer-hook,
+// callers write its extra-account-meta list with init_transfer_hook_metas once the
+// XftAccount is written.
+fn create_xft_mint<'info>(
+    accounts: &XftMintAccounts<'info>,
+    mint_seeds: &[&[u8]],
+    mint_authority_bump: u8,
+    title: &str,
+    ipfs: &str,
+    transfer_rule: TransferRule,
+) -> Result<()> {
+    let token_program = accounts.token_program.to_account_info();
+    let mint = accounts.xft_mint.to_account_info();
+    let mint_authority = accounts.mint_authority.key();
+    let token_2022 = token_program.key() == Token2022::id();
+    require!(token_2022 || transfer_rule == TransferRule::Transferable, ErrorCode::Token2022Required);
+    let mut extensions = Vec::new();
+    if token_2022 {
+        extensions.push(ExtensionType::MetadataPointer);
+        match transfer_rule {
+            TransferRule::Soulbound => extensions.push(ExtensionType::NonTransferable),
+            TransferRule::Restricted => {
+                extensions.push(ExtensionType::PermanentDelegate);
+                extensions.push(ExtensionType::TransferHook);
+            }
+            TransferRule::Transferable => {}
+        }
+    }
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions)?;
+    // The metadata extension is reallocated by Token-2022 itself, the mint only needs its rent
+    let metadata_space = if token_2022 {
+        4 + 32 + 32 + (4 + title.len()) + (4 + XFT_SYMBOL.len()) + (4 + ipfs.len()) + 4
+    } else {
+        0
+    };
+    let lamports = Rent::get()?.minimum_balance(space + metadata_space);
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            accounts.system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: accounts.payer.to_account_info(),
+                to: mint.clone(),
+            },
+            &[mint_seeds],
+        ),
+        lamports,
+        space as u64,
+        &token_program.key(),
+    )?;
+    if token_2022 {
+        metadata_pointer_initialize(
+            CpiContext::new(
+                token_program.clone(),
+                MetadataPointerInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: mint.clone(),
+                },
+            ),
+            Some(mint_authority),
+            Some(mint.key()),
+        )?;
+        match transfer_rule {
+            TransferRule::Soulbound => non_transferable_mint_initialize(CpiContext::new(
+                token_program.clone(),
+                NonTransferableMintInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: mint.clone(),
+                },
+            ))?,
+            TransferRule::Restricted => {
+                permanent_delegate_initialize(
+                    CpiContext::new(
+                        token_program.clone(),
+                        PermanentDelegateInitialize {
+                            token_program_id: token_program.clone(),
+                            mint: mint.clone(),
+                        },
+                    ),
+                    &mint_authority,
+                )?;
+                transfer_hook_initialize(
+                    CpiContext::new(
+                        token_program.clone(),
+                        TransferHookInitialize {
+                            token_program_id: token_program.clone(),
+                            mint: mint.clone(),
+                        },
+                    ),
+                    Some(mint_authority),
+                    Some(Pubkey::from_str(TRANSFER_HOOK_PROGRAM_ID).unwrap()),
+                )?;
+            }
+            TransferRule::Transferable => {}
+        }
+    }
+    token_interface::initialize_mint2(
+        CpiContext::new(token_program.clone(), token_interface::InitializeMint2 { mint: mint.clone() }),
+        0,
+        &mint_authority,
+        None,
+    )?;
+    if token_2022 {
+        let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", &[mint_authority_bump]]];
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TokenMetadataInitialize {
+                    program_id: token_program.clone(),
+                    metadata: mint.clone(),
+                    update_authority: accounts.mint_authority.to_account_info(),
+                    mint_authority: accounts.mint_authority.to_account_info(),
+                    mint: mint.clone(),
+                },
+                signer_seeds,
+            ),
+            title.to_string(),
+            XFT_SYMBOL.to_string(),
+            ipfs.to_string(),
+        )?;
+    }
+    Ok(())
+}
+
+// Pays a protocol fee quoted in lamports into the xft-admin treasury. When an allowlisted
+// payment mint is passed the fee is converted at its mint_fee_rate and paid in that token.
+// Returns the amount paid in the payment currency.
+#[allow(clippy::too_many_arguments)]
+fn pay_protocol_fee<'info>(
+    admin_program: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    treasury_token_account: &Option<AccountInfo<'info>>,
+    payer: &Signer<'info>,
+    payment_mint: &Option<AccountInfo<'info>>,
+    payer_payment_token_account: &Option<Account<'info, TokenAccount>>,
+    token_program: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    lamports: u64,
+) -> Result<u64> {
+    let (fee_amount, treasury_mint) = match payment_mint {
+        // Pay in an allowlisted SPL mint, converted at the xft-admin mint_fee_rate
+        Some(payment_mint) => {
+            let entry = PaymentMint::load(payment_mint)?;
+            require!(entry.enabled && entry.mint_fee_rate > 0, ErrorCode::PaymentMintNotAccepted);
+            (entry.convert_mint_fee(lamports), entry.mint)
+        }
+        None => (lamports, Pubkey::default()),
+    };
+    let (expected_treasury, _) = Pubkey::find_program_address(&[b"treasury", treasury_mint.as_ref()], &admin_xft::ID);
+    require!(treasury.key() == expected_treasury, ErrorCode::InvalidTreasury);
+    let spl_payment = treasury_mint != Pubkey::default();
+    let cpi_ctx = CpiContext::new(
+        admin_program.clone(),
+        admin_xft_cpi::DepositFee {
+            treasury: treasury.clone(),
+            payer: payer.to_account_info(),
+            treasury_token_account: treasury_token_account.clone().filter(|_| spl_payment),
+            payer_token_account: payer_payment_token_account.as_ref().map(|a| a.to_account_info()).filter(|_| spl_payment),
+            token_program: Some(token_program.clone()).filter(|_| spl_payment),
+            system_program: system_program.to_account_info(),
+        },
+    );
+    admin_xft_cpi::cpi::deposit_fee(cpi_ctx, fee_amount)?;
+    Ok(fee_amount)
+}
+
+// Loads the XftAccount for xft_id, checking it is the [b"xft", xft_id] PDA this program minted
+fn load_xft_account(account: &AccountInfo, xft_id: u64) -> Result<XftAccount> {
+    require!(account.owner == &crate::ID, ErrorCode::InvalidLabelAccount);
+    let xft_account = XftAccount::try_deserialize(&mut &account.data.borrow()[..])?;
+    require!(xft_account.xft_id == xft_id, ErrorCode::InvalidLabelAccount);
+    let expected = xft_account_address(&crate::ID, xft_id, xft_account.bump).ok_or(ErrorCode::InvalidLabelAccount)?;
+    require!(account.key() == expected, ErrorCode::InvalidLabelAccount);
+    Ok(xft_account)
+}
+
+// Reads name and URI from the Metaplex metadata PDA of `mint`. The account starts with the key,
+// update authority and mint, followed by the borsh strings name, symbol and uri, all padded
+// with zero bytes.
+fn metaplex_metadata(metadata: &AccountInfo, mint: &Pubkey) -> Result<(String, String)> {
+    let program_id = Pubkey::from_str(METAPLEX_METADATA_PROGRAM_ID).unwrap();
+    let (expected, _) =
+        Pubkey::find_program_address(&[b"metadata", program_id.as_ref(), mint.as_ref()], &program_id);
+    require!(
+        *metadata.owner == program_id && metadata.key() == expected,
+        ErrorCode::InvalidNftMetadata
+    );
+    let data = metadata.try_borrow_data()?;
+    let mut rest: &[u8] = data.get(1 + 32 + 32..).ok_or(ErrorCode::InvalidNftMetadata)?;
+    let mut next = || String::deserialize(&mut rest).map_err(|_| error!(ErrorCode::InvalidNftMetadata));
+    let name = next()?;
+    let _symbol = next()?;
+    let uri = next()?;
+    Ok((
+        name.trim_end_matches('\0').to_string(),
+        uri.trim_end_matches('\0').to_string(),
+    ))
+}
+
+// Sends the rent of a minter-owned account to `destination` and hands the account back to
+// the system program
+fn close_program_account<'info>(account: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination.lamports().checked_add(lamports).unwrap();
+    **account.try_borrow_mut_lamports()? = 0;
+    account.assign(&system_program::ID);
+    account.resize(0)?;
+    Ok(())
+}
+
+pub fn is_market_license(xft_account_info: &AccountInfo, parent_xft_account_info: &AccountInfo) -> Result<(bool, u64, Pubkey, u64)> {
+    let xft_account = XftAccount::try_from_slice(&xft_account_info.data.borrow())?;
+    let settings = xft_account.xft_settings()?;
+    let now = Clock::get()?.unix_timestamp as u64;
+    if settings.is_expired(now) {
+        return Ok((false, 0, Pubkey::default(), 0));
+    }
+    // Get the parent label account
+    let parent_xft_id = settings.label_id;
+    if parent_xft_id == 0 {
+        return Ok((false, 0, Pubkey::default(), 0));
+    }
+    let parent_xft_account = XftAccount::try_from_slice(&parent_xft_account_info.data.borrow())?;
+    let parent_settings = parent_xft_account.xft_settings()?;
+    if parent_settings.is_expired(now) {
+        return Ok((false, parent_xft_id, Pubkey::default(), 0));
+    }
+    // Parent vault and the parent's marketplace fee
+    let parent_vault = parent_xft_account.addresses.get(2).copied().unwrap_or(Pubkey::default());
+    Ok((true, parent_xft_id, parent_vault, parent_settings.marketplace_fee))
+}
+
+pub fn get_vault(xft_account_info: &AccountInfo) -> Result<Pubkey> {
+    let xft_account = XftAccount::try_from_slice(&xft_account_info.data.borrow())?;
+    let addresses = xft_account.addresses;
+    let vault = addresses.get(2).copied().unwrap_or(Pubkey::default());
+    Ok(vault)
+}
+
+// Local mirror of admin_xft::PauseRegistry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PauseRegistry {
+    pub global: bool,
+    pub minting: bool,
+    pub transfers: bool,
+    pub market: bool,
+    pub vault_withdrawals: bool,
+    pub bump: u8,
+}
+
+impl PauseRegistry {
+    /// Loads the xft-admin pause registry; an uninitialized registry means nothing is paused
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        let (expected, _) = Pubkey::find_program_address(&[b"pause_registry"], &admin_xft::ID);
+        require!(account.key() == expected, ErrorCode::InvalidPauseRegistry);
+        if account.data_is_empty() {
+            return Ok(PauseRegistry::default());
+        }
+        require!(*account.owner == admin_xft::ID, ErrorCode::InvalidPauseRegistry);
+        let data = account.try_borrow_data()?;
+        PauseRegistry::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::InvalidPauseRegistry))
+    }
+}
+
+/// Fails if `address` has an xft-admin blocklist entry. The caller passes the entry PDA
+/// for the address, which must be empty (never created, or closed when unblocked).
+pub fn require_not_blocked(blocklist_entry: &AccountInfo, address: &Pubkey) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(&[b"blocklist", address.as_ref()], &admin_xft::ID);
+    require!(blocklist_entry.key() == expected, ErrorCode::InvalidBlocklistEntry);
+    require!(blocklist_entry.data_is_empty(), ErrorCode::AddressBlocked);
+    Ok(())
+}
+
+// Local mirror of admin_xft::TitleReservation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct TitleReservation {
+    pub title: String,
+    pub reserved_for: Pubkey,
+    pub premium_fee_per_year: u64,
+    pub bump: u8,
+}
+
+impl TitleReservation {
+    /// Loads the xft-admin reservation PDA for `title`, None if the title is not reserved
+    pub fn load(account: &AccountInfo, title: &str) -> Result<Option<Self>> {
+        let (expected, _) = Pubkey::find_program_address(&[b"title_reservation", title_seed(title).as_ref()], &admin_xft::ID);
+        require!(account.key() == expected, ErrorCode::InvalidTitleReservation);
+        if account.data_is_empty() {
+            return Ok(None);
+        }
+        require!(*account.owner == admin_xft::ID, ErrorCode::InvalidTitleReservation);
+        let data = account.try_borrow_data()?;
+        let reservation = TitleReservation::deserialize(&mut &data[8..])
+            .map_err(|_| error!(ErrorCode::InvalidTitleReservation))?;
+        Ok(Some(reservation))
+    }
+}
+
+// The children of a label are only changed by xft-market listings, signed by its escrow authority
+fn require_market_escrow_authority(market_authority: &AccountInfo) -> Result<()> {
+    let market_program_id = Pubkey::from_str(MARKET_PROGRAM_ID).unwrap();
+    let (expected, _) = Pubkey::find_program_address(&[b"escrow_authority"], &market_program_id);
+    require!(market_authority.key() == expected, ErrorCode::Unauthorized);
+    Ok(())
+}
+
+// xft-admin::get_mint_fee falls back to the flat fee when its schedule account is empty, so
+// the account passed for the label type has to be its [b"mint_fee", label_type] PDA
+fn require_fee_schedule(fee_schedule: &AccountInfo, label_type: LabelType) -> Result<()> {
+    let (expected, _) =
+        Pubkey::find_program_address(&[b"mint_fee", label_type.as_u64().to_le_bytes().as_ref()], &admin_xft::ID);
+    require!(fee_schedule.key() == expected, ErrorCode::InvalidFeeSchedule);
+    Ok(())
+}
+
+// Local mirror of admin_xft::PaymentMint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PaymentMint {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub marketplace_fee_bps: u64,
+    pub mint_fee_rate: u64,
+    pub enabled: bool,
+    pub bump: u8,
+}
+
+impl PaymentMint {
+    /// Loads an xft-admin payment mint entry and checks it sits at its PDA
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        require!(*account.owner == admin_xft::ID, ErrorCode::PaymentMintNotAccepted);
+        let data = account.try_borrow_data()?;
+        let entry = PaymentMint::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::PaymentMintNotAccepted))?;
+        let (expected, _) = Pubkey::find_program_address(&[b"payment_mint", entry.mint.as_ref()], &admin_xft::ID);
+        require!(account.key() == expected, ErrorCode::PaymentMintNotAccepted);
+        Ok(entry)
+    }
+
+    /// Converts a lamport-denominated mint fee into this mint's base units
+    pub fn convert_mint_fee(&self, lamports: u64) -> u64 {
+        (lamports as u128 * self.mint_fee_rate as u128 / 1_000_000_000) as u64
+    }
+}
+
+// Account structs for Anchor instructions
+
+#[account]
+pub struct XftAccount {
+    pub xft_id: u64,
     pub settings: Vec<u64>,
     pub addresses: Vec<Pubkey>,
     pub ipfs: String,
     pub bump: u8,
 }
 
+impl XftAccount {
+    pub fn xft_settings(&self) -> Result<XftSettings> {
+        XftSettings::decode(&self.settings)
+    }
+
+    pub fn set_xft_settings(&mut self, settings: &XftSettings) {
+        self.settings = settings.encode();
+    }
+}
+
 #[account]
 pub struct TitleLookup {
     pub xft_id: u64,
     pub bump: u8,
 }
 
+/// Print state of a limited edition master XftAccount
+#[account]
+pub struct MasterEdition {
+    pub xft_id: u64,
+    pub max_supply: u64,
+    pub printed: u64,
+    /// Editions burned by burn_edition
+    pub burned: u64,
+    pub bump: u8,
+}
+
+impl MasterEdition {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 1;
+}
+
+/// Numbered edition `number` of `max_supply` printed from master xft_id
+#[account]
+pub struct Edition {
+    pub xft_id: u64,
+    pub number: u64,
+    pub max_supply: u64,
+    /// Single token mint of this edition, [b"edition_mint", xft_id, number]; its holder owns the edition
+    pub mint: Pubkey,
+    /// Edition vault, default when printed without one
+    pub vault: Pubkey,
+    pub bump: u8,
+}
+
+impl Edition {
+    pub const LEN: usize = 8 + 8 + 8 + 32 + 32 + 1;
+}
+
+/// External NFT held in the wrap escrow for the type 8 XFT xft_id until redeem_nft
+#[account]
+pub struct WrappedNft {
+    pub xft_id: u64,
+    pub original_mint: Pubkey,
+    /// Metaplex metadata URI at wrap time, empty for NFTs without Metaplex metadata
+    pub metadata_uri: String,
+    pub depositor: Pubkey,
+    pub bump: u8,
+}
+
+impl WrappedNft {
+    pub const LEN: usize = 8 + 32 + (4 + MAX_METADATA_URI_LEN) + 32 + 1;
+}
+
+/// Metadata version, the last MAX_METADATA_HISTORY URIs (oldest first) and the one-way freeze
+#[account]
+pub struct MetadataHistory {
+    pub xft_id: u64,
+    pub version: u64,
+    pub frozen: bool,
+    pub previous_uris: Vec<String>,
+    pub bump: u8,
+}
+
+impl MetadataHistory {
+    pub const LEN: usize = 8 + 8 + 1 + 4 + MAX_METADATA_HISTORY * (4 + MAX_METADATA_URI_LEN) + 1;
+}
+
 #[account]
 pub struct Counter {
     pub value: u64,
@@ -458,25 +2088,36 @@ pub struct MintXft<'info> {
     pub authority: Signer<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
-    #[account(mut)]
-    pub xft_mint: AccountInfo<'info>,
+    /// Next xft_id, seeds the XftAccount and mint PDAs
+    #[account(mut, seeds = [b"counter"], bump)]
+    pub counter: Account<'info, Counter>,
     #[account(
         init,
         payer = payer,
         space = 8 + 8 + (8 + 32) * 16 + 8 + 32 * 8 + 1, // adjust as needed
-        seeds = [b"xft", authority.key().as_ref()],
+        seeds = [b"xft", counter.value.to_le_bytes().as_ref()],
         bump
     )]
     pub xft: Account<'info, XftAccount>,
-    #[account(mut, seeds = [b"counter"], bump)]
-    pub counter: Account<'info, Counter>,
+    /// CHECK: PDA mint authority of every XFT mint, signs mint_to in mint_xft
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+    /// CHECK: one SPL or Token-2022 mint per xft_id, 0 decimals, created in create_xft_mint
+    #[account(
+        mut,
+        seeds = [b"xft_mint", counter.value.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub xft_mint: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     /// CHECK: This is safe for prototyping; actual checks should be implemented in production
     pub operator_program: AccountInfo<'info>,
     /// CHECK: This is safe for prototyping; actual checks should be implemented in production
     pub operator_account: AccountInfo<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    /// CHECK: XftAccount of the linked label (settings[0]), verified in label_owned_by
+    pub label_account: AccountInfo<'info>,
+    /// CHECK: xft-vault program, used for CPI only
+    #[account(address = vault::ID)]
     pub vault_program: AccountInfo<'info>,
     /// CHECK: This is safe for prototyping; actual checks should be implemented in production
     #[account(mut)]
@@ -485,58 +2126,288 @@ pub struct MintXft<'info> {
     pub label_owner: AccountInfo<'info>,
     /// CHECK: This is safe for prototyping; actual checks should be implemented in production
     pub caller: AccountInfo<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
-    pub xft_token_account: AccountInfo<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
-    pub caller_token_account: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    /// CHECK: caller's associated token account for xft_mint, created in mint_xft
+    #[account(mut)]
+    pub caller_token_account: UncheckedAccount<'info>,
+    /// SPL Token, or Token-2022 to mint with metadata and transfer extensions
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// CHECK: xft-admin program, quotes the mint fee and receives it
+    #[account(address = admin_xft::ID)]
     pub admin_program: AccountInfo<'info>,
     /// CHECK: This is safe for prototyping; actual checks should be implemented in production
     pub admin_account: AccountInfo<'info>,
+    /// CHECK: xft-admin MintFeeSchedule PDA for settings[3], checked in require_fee_schedule
+    pub fee_schedule: AccountInfo<'info>,
+    /// Title lookup claimed by labels; omit for items (settings[3] types 7 and 8)
     #[account(
         init_if_needed,
         payer = payer,
         space = 8 + 8 + 1, // adjust as needed
-        seeds = [b"title_lookup", title.as_bytes()],
+        seeds = [b"title_lookup", &title_seed(&title)[..]],
         bump
     )]
-    pub title_lookup: Account<'info, TitleLookup>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    pub title_lookup: Option<Account<'info, TitleLookup>>,
+    /// CHECK: xft-admin fee treasury for the payment mint, PDA checked in mint_xft
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+    /// CHECK: xft-admin treasury token account, validated by xft-admin::deposit_fee
+    #[account(mut)]
+    pub treasury_token_account: Option<AccountInfo<'info>>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+    /// CHECK: xft-admin PaymentMint entry, checked in PaymentMint::load; omit to pay in SOL
+    pub payment_mint: Option<AccountInfo<'info>>,
+    #[account(mut)]
+    pub payer_payment_token_account: Option<Account<'info, TokenAccount>>,
+    /// CHECK: xft-admin blocklist PDA for authority, checked in require_not_blocked
+    pub authority_blocklist_entry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for label_owner, checked in require_not_blocked
+    pub label_owner_blocklist_entry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for caller, checked in require_not_blocked
+    pub caller_blocklist_entry: AccountInfo<'info>,
+    /// CHECK: xft-admin title reservation PDA for the normalized title, checked in TitleReservation::load
+    pub title_reservation: AccountInfo<'info>,
+    /// CHECK: extra-account-meta list PDA of the new mint under xft-transfer-hook, created by
+    /// initialize_extra_account_meta_list; restricted items only
     #[account(mut)]
-    pub payout_account: AccountInfo<'info>,
+    pub extra_account_meta_list: Option<UncheckedAccount<'info>>,
+    /// CHECK: xft-transfer-hook program, used for CPI only; restricted items only
+    #[account(address = xft_transfer_hook::ID)]
+    pub transfer_hook_program: Option<AccountInfo<'info>>,
 }
 
 #[derive(Accounts)]
 pub struct WrapXft<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
-    #[account(mut)]
+    #[account(mut, seeds = [b"xft", parent_xft.xft_id.to_le_bytes().as_ref()], bump = parent_xft.bump)]
     pub parent_xft: Account<'info, XftAccount>,
-    #[account(init, payer = authority, space = 8 + 8 + (8 + 32) * 16 + 8 + 32 * 8 + 1, seeds = [b"xft", authority.key().as_ref(), b"wrapped"], bump)]
-    pub wrapped_xft: Account<'info, XftAccount>,
     #[account(mut, seeds = [b"counter"], bump)]
     pub counter: Account<'info, Counter>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    #[account(init, payer = authority, space = 8 + 8 + (8 + 32) * 16 + 8 + 32 * 8 + 1, seeds = [b"xft", counter.value.to_le_bytes().as_ref()], bump)]
+    pub wrapped_xft: Account<'info, XftAccount>,
+    /// CHECK: vault PDA of the wrapped xft_id, created by xft-vault::create_vault
     #[account(mut)]
     pub vault: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    /// CHECK: xft-vault program, used for CPI only
+    #[account(address = vault::ID)]
     pub vault_program: AccountInfo<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    #[account(mut, token::mint = parent_mint, token::authority = wrapper, token::token_program = token_program)]
+    pub user_xft_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(seeds = [b"xft_mint", parent_xft.xft_id.to_le_bytes().as_ref()], bump, mint::token_program = token_program)]
+    pub parent_mint: InterfaceAccount<'info, token_interface::Mint>,
+    /// CHECK: PDA owning the wrap escrow token accounts
+    #[account(seeds = [b"wrap_authority"], bump)]
+    pub wrap_authority: AccountInfo<'info>,
+    /// Holds the parent tokens until unwrap_xft
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = parent_mint,
+        associated_token::authority = wrap_authority,
+        associated_token::token_program = token_program
+    )]
+    pub wrap_escrow_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub wrapper: Signer<'info>,
+    /// CHECK: PDA mint authority of every XFT mint, mints the wrapped supply
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+    /// CHECK: mint of the wrapped xft_id, created in create_xft_mint
+    #[account(mut, seeds = [b"xft_mint", counter.value.to_le_bytes().as_ref()], bump)]
+    pub wrapped_mint: UncheckedAccount<'info>,
+    /// CHECK: wrapper's associated token account for wrapped_mint, created in wrap_xft
     #[account(mut)]
-    pub user_xft_token_account: AccountInfo<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    pub user_wrapped_token_account: UncheckedAccount<'info>,
+    /// Program of the parent mint, the wrapped mint is created with the same one
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+    /// CHECK: XftAccount of the parent's label (settings[0]), read by xft-transfer-hook; restricted parents only
+    pub label_account: Option<AccountInfo<'info>>,
+    /// CHECK: extra-account-meta list PDA of the new mint under xft-transfer-hook, created by
+    /// initialize_extra_account_meta_list; restricted items only
     #[account(mut)]
-    pub burn_address: AccountInfo<'info>,
-    pub wrapper: Signer<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    pub extra_account_meta_list: Option<UncheckedAccount<'info>>,
+    /// CHECK: xft-transfer-hook program, used for CPI only; restricted items only
+    #[account(address = xft_transfer_hook::ID)]
+    pub transfer_hook_program: Option<AccountInfo<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct UnwrapXft<'info> {
     #[account(mut)]
-    pub wrapped_mint: AccountInfo<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    pub holder: Signer<'info>,
+    #[account(mut, seeds = [b"xft", parent_xft.xft_id.to_le_bytes().as_ref()], bump = parent_xft.bump)]
+    pub parent_xft: Account<'info, XftAccount>,
+    /// Closed to the holder once unwrapped
+    #[account(
+        mut,
+        seeds = [b"xft", wrapped_xft.xft_id.to_le_bytes().as_ref()],
+        bump = wrapped_xft.bump,
+        close = holder
+    )]
+    pub wrapped_xft: Account<'info, XftAccount>,
+    #[account(seeds = [b"xft_mint", parent_xft.xft_id.to_le_bytes().as_ref()], bump, mint::token_program = token_program)]
+    pub parent_mint: InterfaceAccount<'info, token_interface::Mint>,
+    /// CHECK: PDA owning the wrap escrow token accounts
+    #[account(seeds = [b"wrap_authority"], bump)]
+    pub wrap_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        associated_token::mint = parent_mint,
+        associated_token::authority = wrap_authority,
+        associated_token::token_program = token_program
+    )]
+    pub wrap_escrow_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut, token::mint = parent_mint, token::token_program = token_program)]
+    pub holder_parent_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"xft_mint", wrapped_xft.xft_id.to_le_bytes().as_ref()],
+        bump,
+        mint::token_program = token_program
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(mut, token::mint = wrapped_mint, token::authority = holder, token::token_program = token_program)]
+    pub holder_wrapped_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    /// CHECK: wrapped vault (wrapped_xft addresses[2]), checked in unwrap_xft; required when set
     #[account(mut)]
-    pub user_wrapped_token_account: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
+    pub vault: Option<AccountInfo<'info>>,
+    /// CHECK: PDA signing xft-vault::close_vault
+    #[account(seeds = [b"vault_authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+    /// CHECK: xft-vault program, used for CPI only
+    #[account(address = vault::ID)]
+    pub vault_program: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WrapNft<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    #[account(mut, seeds = [b"counter"], bump)]
+    pub counter: Account<'info, Counter>,
+    #[account(
+        init,
+        payer = depositor,
+        space = 8 + 8 + (8 + 32) * 16 + 8 + 32 * 8 + 1,
+        seeds = [b"xft", counter.value.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub xft: Account<'info, XftAccount>,
+    #[account(
+        init,
+        payer = depositor,
+        space = 8 + WrappedNft::LEN,
+        seeds = [b"wrapped_nft", counter.value.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub wrapped_nft: Account<'info, WrappedNft>,
+    #[account(mint::token_program = nft_token_program)]
+    pub nft_mint: InterfaceAccount<'info, token_interface::Mint>,
+    /// CHECK: Metaplex metadata PDA of nft_mint, checked in metaplex_metadata; omit for plain SPL NFTs
+    pub nft_metadata: Option<AccountInfo<'info>>,
+    #[account(
+        mut,
+        token::mint = nft_mint,
+        token::authority = depositor,
+        token::token_program = nft_token_program
+    )]
+    pub depositor_nft_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    /// CHECK: PDA owning the wrap escrow token accounts
+    #[account(seeds = [b"wrap_authority"], bump)]
+    pub wrap_authority: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = nft_mint,
+        associated_token::authority = wrap_authority,
+        associated_token::token_program = nft_token_program
+    )]
+    pub nft_escrow: InterfaceAccount<'info, token_interface::TokenAccount>,
+    /// SPL Token or Token-2022, whichever owns nft_mint
+    pub nft_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: PDA mint authority of every XFT mint
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+    /// CHECK: mint of the wrapped XFT, created in create_xft_mint
+    #[account(mut, seeds = [b"xft_mint", counter.value.to_le_bytes().as_ref()], bump)]
+    pub xft_mint: UncheckedAccount<'info>,
+    /// CHECK: depositor's associated token account for xft_mint, created in wrap_nft
+    #[account(mut)]
+    pub depositor_xft_token_account: UncheckedAccount<'info>,
+    /// SPL Token, or Token-2022 to mint with metadata
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: vault PDA of the new xft_id, created by xft-vault::create_vault
+    #[account(mut)]
+    pub vault: AccountInfo<'info>,
+    /// CHECK: xft-vault program, used for CPI only
+    #[account(address = vault::ID)]
+    pub vault_program: AccountInfo<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for depositor, checked in require_not_blocked
+    pub depositor_blocklist_entry: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64)]
+pub struct RedeemNft<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    #[account(mut, seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump = xft_account.bump, close = holder)]
+    pub xft_account: Account<'info, XftAccount>,
+    #[account(mut, seeds = [b"wrapped_nft", xft_id.to_le_bytes().as_ref()], bump = wrapped_nft.bump, close = holder)]
+    pub wrapped_nft: Account<'info, WrappedNft>,
+    #[account(mut, seeds = [b"xft_mint", xft_id.to_le_bytes().as_ref()], bump)]
+    pub xft_mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(mut, token::mint = xft_mint, token::authority = holder, token::token_program = token_program)]
+    pub holder_xft_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = wrapped_nft.original_mint, mint::token_program = nft_token_program)]
+    pub nft_mint: InterfaceAccount<'info, token_interface::Mint>,
+    /// CHECK: PDA owning the wrap escrow token accounts
+    #[account(seeds = [b"wrap_authority"], bump)]
+    pub wrap_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = wrap_authority,
+        associated_token::token_program = nft_token_program
+    )]
+    pub nft_escrow: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = holder,
+        associated_token::mint = nft_mint,
+        associated_token::authority = holder,
+        associated_token::token_program = nft_token_program
+    )]
+    pub holder_nft_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub nft_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: vault of xft_id (addresses[2]), closed by xft-vault::close_vault
+    #[account(mut, address = xft_account.addresses[2] @ ErrorCode::InvalidVault)]
+    pub vault: AccountInfo<'info>,
+    /// CHECK: PDA signing xft-vault::close_vault
+    #[account(seeds = [b"vault_authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+    /// CHECK: xft-vault program, used for CPI only
+    #[account(address = vault::ID)]
+    pub vault_program: AccountInfo<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for holder, checked in require_not_blocked
+    pub holder_blocklist_entry: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
@@ -549,17 +2420,52 @@ pub struct TransferXft<'info> {
     /// CHECK: This is safe for prototyping; actual checks should be implemented in production
     #[account(mut)]
     pub receiver: AccountInfo<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
-    #[account(mut)]
+    #[account(mut, seeds = [b"xft", xft_account.xft_id.to_le_bytes().as_ref()], bump = xft_account.bump)]
     pub xft_account: Account<'info, XftAccount>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    /// CHECK: XftAccount of the item's label (settings[0]), verified in load_xft_account
     #[account(mut)]
     pub parent_xft_account: AccountInfo<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
-    pub operator_program: AccountInfo<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
-    pub burn_address: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
+    /// CHECK: xft-operator entry of caller for the label, checked in active_operator_role
+    pub operator_account: Option<AccountInfo<'info>>,
+    #[account(seeds = [b"xft_mint", xft_account.xft_id.to_le_bytes().as_ref()], bump)]
+    pub xft_mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(mut, token::mint = xft_mint, token::token_program = token_program)]
+    pub receiver_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for caller, checked in require_not_blocked
+    pub caller_blocklist_entry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for receiver, checked in require_not_blocked
+    pub receiver_blocklist_entry: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecallXft<'info> {
+    pub label_owner: Signer<'info>,
+    #[account(seeds = [b"xft", xft_account.xft_id.to_le_bytes().as_ref()], bump = xft_account.bump)]
+    pub xft_account: Account<'info, XftAccount>,
+    /// CHECK: XftAccount of the item's label (settings[0]), verified in label_owned_by
+    pub label_account: AccountInfo<'info>,
+    #[account(seeds = [b"xft_mint", xft_account.xft_id.to_le_bytes().as_ref()], bump)]
+    pub xft_mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(mut, token::mint = xft_mint, token::token_program = token_program)]
+    pub holder_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        mut,
+        token::mint = xft_mint,
+        token::authority = label_owner,
+        token::token_program = token_program
+    )]
+    pub label_owner_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    /// CHECK: PDA mint authority and permanent delegate of restricted Token-2022 mints
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+    pub token_program: Program<'info, Token2022>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for label_owner, checked in require_not_blocked
+    pub label_owner_blocklist_entry: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
@@ -580,14 +2486,359 @@ pub struct CheckOperator<'info> {
     pub operator_account: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct UpdateLabelChildren<'info> {
+    /// xft-market escrow authority PDA, checked in require_market_escrow_authority
+    pub market_authority: Signer<'info>,
+    /// CHECK: XftAccount for xft_id, owner, id and PDA verified in load_xft_account
+    #[account(mut)]
+    pub label_account: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct IsLabelOwner<'info> {
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    /// CHECK: XftAccount for xft_id, owner, id and PDA verified in label_owned_by
     pub label_account: AccountInfo<'info>,
     /// The authority to check
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+#[instruction(xft_id: u64, title: String)]
+pub struct RenewLabel<'info> {
+    /// Anyone can pay for a renewal
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: XftAccount for xft_id, owner, id and PDA verified in renew_label
+    #[account(mut)]
+    pub label_account: AccountInfo<'info>,
+    /// Title lookup of the label, ties `title` to xft_id
+    #[account(seeds = [b"title_lookup", &title_seed(&title)[..]], bump = title_lookup.bump)]
+    pub title_lookup: Account<'info, TitleLookup>,
+    /// CHECK: xft-admin program, used for CPI only
+    #[account(address = admin_xft::ID)]
+    pub admin_program: AccountInfo<'info>,
+    /// CHECK: xft-admin AdminXFT account, validated by xft-admin::get_mint_fee
+    pub admin_account: AccountInfo<'info>,
+    /// CHECK: xft-admin MintFeeSchedule PDA for the label type, checked in require_fee_schedule
+    pub fee_schedule: AccountInfo<'info>,
+    /// CHECK: xft-admin title reservation PDA for the normalized title, checked in TitleReservation::load
+    pub title_reservation: AccountInfo<'info>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for payer, checked in require_not_blocked
+    pub payer_blocklist_entry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for the label owner (addresses[1]), checked in require_not_blocked
+    pub label_owner_blocklist_entry: AccountInfo<'info>,
+    /// CHECK: xft-admin fee treasury for the payment mint, PDA checked in pay_protocol_fee
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+    /// CHECK: xft-admin treasury token account, validated by xft-admin::deposit_fee
+    #[account(mut)]
+    pub treasury_token_account: Option<AccountInfo<'info>>,
+    /// CHECK: xft-admin PaymentMint entry, checked in PaymentMint::load; omit to pay in SOL
+    pub payment_mint: Option<AccountInfo<'info>>,
+    #[account(mut)]
+    pub payer_payment_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimLabel<'info> {
+    /// xft-market reclaim authority PDA, checked in reclaim_label
+    pub market_authority: Signer<'info>,
+    /// CHECK: XftAccount for xft_id, owner, id and PDA verified in reclaim_label
+    #[account(mut)]
+    pub label_account: AccountInfo<'info>,
+    /// CHECK: label vault (addresses[2]), checked in reclaim_label; omit when the label has none
+    #[account(mut)]
+    pub vault: Option<AccountInfo<'info>>,
+    /// CHECK: PDA signing xft-vault::hand_over_vault
+    #[account(seeds = [b"vault_authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+    /// CHECK: xft-vault program, used for CPI only
+    #[account(address = vault::ID)]
+    pub vault_program: AccountInfo<'info>,
+    /// CHECK: owner before an earlier reclaim with contents still unclaimed, verified by
+    /// xft-vault::hand_over_vault; omit when nothing is claimable
+    #[account(mut)]
+    pub previous_claimant: Option<AccountInfo<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64)]
+pub struct BurnXft<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    #[account(
+        mut,
+        token::mint = xft_mint,
+        token::authority = holder,
+        token::token_program = token_program
+    )]
+    pub holder_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(mut, seeds = [b"xft_mint", xft_id.to_le_bytes().as_ref()], bump)]
+    pub xft_mint: InterfaceAccount<'info, token_interface::Mint>,
+    /// CHECK: XftAccount for xft_id, owner, id and PDA verified in burn_xft
+    #[account(mut)]
+    pub xft_account: AccountInfo<'info>,
+    /// CHECK: XFT owner (addresses[1]), receives the rent when the last edition is burned
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+    /// Title lookup of a label, released with the last edition; omit for items
+    #[account(mut)]
+    pub title_lookup: Option<Account<'info, TitleLookup>>,
+    /// CHECK: XFT vault (addresses[2]), checked in burn_xft; omit when the XFT has none
+    #[account(mut)]
+    pub vault: Option<AccountInfo<'info>>,
+    /// CHECK: PDA signing xft-vault::close_vault
+    #[account(seeds = [b"vault_authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+    /// CHECK: xft-vault program, used for CPI only
+    #[account(address = vault::ID)]
+    pub vault_program: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64)]
+pub struct PrintEdition<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump = xft_account.bump)]
+    pub xft_account: Account<'info, XftAccount>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MasterEdition::LEN,
+        seeds = [b"master_edition", xft_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub master_edition: Account<'info, MasterEdition>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Edition::LEN,
+        seeds = [b"edition", xft_id.to_le_bytes().as_ref(), (master_edition.printed + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub edition: Account<'info, Edition>,
+    /// CHECK: mint of the printed edition, created in the handler by create_xft_mint
+    #[account(
+        mut,
+        seeds = [b"edition_mint", xft_id.to_le_bytes().as_ref(), (master_edition.printed + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub edition_mint: UncheckedAccount<'info>,
+    /// CHECK: PDA mint authority of every XFT mint
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+    /// CHECK: owner of the printed edition
+    pub recipient: AccountInfo<'info>,
+    /// CHECK: recipient's associated token account for edition_mint, created in the handler
+    #[account(mut)]
+    pub recipient_token_account: UncheckedAccount<'info>,
+    /// CHECK: edition vault PDA, created by xft-vault::create_edition_vault; omit without vault
+    #[account(mut)]
+    pub vault: Option<AccountInfo<'info>>,
+    /// CHECK: PDA that signs xft-vault calls
+    #[account(seeds = [b"vault_authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+    /// CHECK: xft-vault program, used for CPI only
+    #[account(address = vault::ID)]
+    pub vault_program: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for authority, checked in require_not_blocked
+    pub authority_blocklist_entry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for recipient, checked in require_not_blocked
+    pub recipient_blocklist_entry: AccountInfo<'info>,
+    /// CHECK: XftAccount of the item's label (settings[0]), read by xft-transfer-hook; restricted items only
+    pub label_account: Option<AccountInfo<'info>>,
+    /// CHECK: extra-account-meta list PDA of the new mint under xft-transfer-hook, created by
+    /// initialize_extra_account_meta_list; restricted items only
+    #[account(mut)]
+    pub extra_account_meta_list: Option<UncheckedAccount<'info>>,
+    /// CHECK: xft-transfer-hook program, used for CPI only; restricted items only
+    #[account(address = xft_transfer_hook::ID)]
+    pub transfer_hook_program: Option<AccountInfo<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64, number: u64)]
+pub struct TransferEdition<'info> {
+    pub owner: Signer<'info>,
+    #[account(seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump = xft_account.bump)]
+    pub xft_account: Account<'info, XftAccount>,
+    #[account(
+        seeds = [b"edition", xft_id.to_le_bytes().as_ref(), number.to_le_bytes().as_ref()],
+        bump = edition.bump
+    )]
+    pub edition: Account<'info, Edition>,
+    /// CHECK: XftAccount of the item's label (settings[0]), checked in require_can_send for restricted items
+    pub label_account: AccountInfo<'info>,
+    /// CHECK: xft-operator entry of owner for the label, checked in active_operator_role
+    pub operator_account: Option<AccountInfo<'info>>,
+    #[account(address = edition.mint)]
+    pub edition_mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        mut,
+        token::mint = edition_mint,
+        token::authority = owner,
+        token::token_program = token_program
+    )]
+    pub owner_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    /// CHECK: new owner of the edition
+    pub receiver: AccountInfo<'info>,
+    #[account(
+        mut,
+        token::mint = edition_mint,
+        token::authority = receiver,
+        token::token_program = token_program
+    )]
+    pub receiver_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for owner, checked in require_not_blocked
+    pub owner_blocklist_entry: AccountInfo<'info>,
+    /// CHECK: xft-admin blocklist PDA for receiver, checked in require_not_blocked
+    pub receiver_blocklist_entry: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64, number: u64)]
+pub struct BurnEdition<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    /// CHECK: master XftAccount for xft_id, owner, id and PDA verified in burn_edition
+    #[account(mut)]
+    pub xft_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"master_edition", xft_id.to_le_bytes().as_ref()],
+        bump = master_edition.bump
+    )]
+    pub master_edition: Account<'info, MasterEdition>,
+    #[account(
+        mut,
+        seeds = [b"edition", xft_id.to_le_bytes().as_ref(), number.to_le_bytes().as_ref()],
+        bump = edition.bump,
+        close = owner
+    )]
+    pub edition: Account<'info, Edition>,
+    #[account(mut, address = edition.mint)]
+    pub edition_mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        mut,
+        token::mint = edition_mint,
+        token::authority = holder,
+        token::token_program = token_program
+    )]
+    pub holder_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    /// CHECK: XFT owner (addresses[1]), verified in burn_edition; receives the rent of what is closed
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+    /// Title lookup of a label, released with the last edition of the run; omit for items
+    #[account(mut)]
+    pub title_lookup: Option<Account<'info, TitleLookup>>,
+    /// CHECK: edition vault (Edition::vault), checked in burn_edition; omit when the edition has none
+    #[account(mut)]
+    pub vault: Option<AccountInfo<'info>>,
+    /// CHECK: PDA signing xft-vault::close_edition_vault
+    #[account(seeds = [b"vault_authority"], bump)]
+    pub vault_authority: AccountInfo<'info>,
+    /// CHECK: xft-vault program, used for CPI only
+    #[account(address = vault::ID)]
+    pub vault_program: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64)]
+pub struct UpdateMetadata<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut, seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump = xft_account.bump)]
+    pub xft_account: Account<'info, XftAccount>,
+    /// CHECK: XftAccount of the governing label (xft_account itself for labels), verified in label_owned_by
+    pub label_account: AccountInfo<'info>,
+    /// CHECK: xft-operator entry of authority for the label, checked in active_operator_role
+    pub operator_account: Option<AccountInfo<'info>>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MetadataHistory::LEN,
+        seeds = [b"metadata_history", xft_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub metadata_history: Account<'info, MetadataHistory>,
+    #[account(
+        mut,
+        seeds = [b"xft_mint", xft_id.to_le_bytes().as_ref()],
+        bump,
+        mint::token_program = token_program
+    )]
+    pub xft_mint: InterfaceAccount<'info, token_interface::Mint>,
+    /// CHECK: PDA mint authority and metadata update authority of XFT mints
+    #[account(seeds = [b"mint_authority"], bump)]
+    pub mint_authority: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64)]
+pub struct FreezeMetadata<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump = xft_account.bump)]
+    pub xft_account: Account<'info, XftAccount>,
+    /// CHECK: XftAccount of the governing label (xft_account itself for labels), verified in label_owned_by
+    pub label_account: AccountInfo<'info>,
+    /// CHECK: xft-operator entry of authority for the label, checked in active_operator_role
+    pub operator_account: Option<AccountInfo<'info>>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MetadataHistory::LEN,
+        seeds = [b"metadata_history", xft_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub metadata_history: Account<'info, MetadataHistory>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(legacy_title: String)]
+pub struct MigrateTitleLookup<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, seeds = [b"title_lookup", legacy_title.as_bytes()], bump, close = payer)]
+    pub legacy_title_lookup: Account<'info, TitleLookup>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + 8 + 1,
+        seeds = [b"title_lookup", &title_seed(&legacy_title)[..]],
+        bump
+    )]
+    pub title_lookup: Account<'info, TitleLookup>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct InitializeCounter<'info> {
     #[account(init, payer = payer, space = 8 + 8, seeds = [b"counter"], bump)]
@@ -601,50 +2852,210 @@ pub struct InitializeCounter<'info> {
 pub struct UpdateVault<'info> {
     /// CHECK: This is safe for prototyping; actual checks should be implemented in production
     pub vault_program: AccountInfo<'info>,
-    #[account(mut)]
+    #[account(mut, seeds = [b"xft", xft_account.xft_id.to_le_bytes().as_ref()], bump = xft_account.bump)]
     pub xft_account: Account<'info, XftAccount>,
 }
 
-// CPI context for xft-admin get_fees
+// CPI helpers for xft-admin views, results are read back from return data
 pub mod admin_xft_cpi {
     use super::*;
+    use anchor_lang::solana_program::hash::hash;
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+    use anchor_lang::solana_program::program::get_return_data;
+
     pub mod cpi {
         use super::*;
         pub fn get_fees<'info>(
             ctx: CpiContext<'_, '_, '_, 'info, GetFees<'info>>,
-        ) -> Result<(u64, u64, Pubkey)> {
-            let ix = anchor_lang::solana_program::instruction::Instruction {
+        ) -> Result<(u64, u64, u64, Pubkey, Pubkey)> {
+            let ix = Instruction {
                 program_id: ctx.program.key(),
-                accounts: vec![anchor_lang::solana_program::instruction::AccountMeta::new_readonly(ctx.accounts.admin.key(), false)],
-                data: vec![0], // get_fees is usually the first instruction, so 0
+                accounts: vec![AccountMeta::new_readonly(ctx.accounts.admin.key(), false)],
+                data: sighash("get_fees").to_vec(),
             };
-            let mut data = [0u8; 8];
-            let acc_infos = ctx.to_account_infos();
-            anchor_lang::solana_program::program::invoke(&ix, &acc_infos)?;
-            // In real Anchor CPI, you would use the generated CPI interface, but here we stub the return
-            // You must replace this with the actual Anchor CPI call if you generate the IDL
-            Ok((0, 0, Pubkey::default())) // placeholder
+            invoke(&ix, &ctx.to_account_infos())?;
+            read_return_data(&ctx.program.key())
         }
+
+        pub fn get_mint_fee<'info>(
+            ctx: CpiContext<'_, '_, '_, 'info, GetMintFee<'info>>,
+            label_type: u64,
+            title_len: u64,
+            years: u64,
+        ) -> Result<u64> {
+            let mut data = sighash("get_mint_fee").to_vec();
+            (label_type, title_len, years).serialize(&mut data)?;
+            let ix = Instruction {
+                program_id: ctx.program.key(),
+                accounts: vec![
+                    AccountMeta::new_readonly(ctx.accounts.admin.key(), false),
+                    AccountMeta::new_readonly(ctx.accounts.fee_schedule.key(), false),
+                ],
+                data,
+            };
+            invoke(&ix, &ctx.to_account_infos())?;
+            read_return_data(&ctx.program.key())
+        }
+
+        pub fn deposit_fee<'info>(
+            ctx: CpiContext<'_, '_, '_, 'info, DepositFee<'info>>,
+            amount: u64,
+        ) -> Result<()> {
+            let mut data = sighash("deposit_fee").to_vec();
+            amount.serialize(&mut data)?;
+            // Optional accounts that are not used are passed as the xft-admin program id
+            let program_id = ctx.program.key();
+            let optional = |account: &Option<AccountInfo<'info>>, writable: bool| match account {
+                Some(info) if writable => AccountMeta::new(info.key(), false),
+                Some(info) => AccountMeta::new_readonly(info.key(), false),
+                None => AccountMeta::new_readonly(program_id, false),
+            };
+            let ix = Instruction {
+                program_id,
+                accounts: vec![
+                    AccountMeta::new(ctx.accounts.treasury.key(), false),
+                    AccountMeta::new(ctx.accounts.payer.key(), true),
+                    optional(&ctx.accounts.treasury_token_account, true),
+                    optional(&ctx.accounts.payer_token_account, true),
+                    optional(&ctx.accounts.token_program, false),
+                    AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+                ],
+                data,
+            };
+            invoke(&ix, &ctx.to_account_infos())?;
+            Ok(())
+        }
+    }
+
+    fn sighash(name: &str) -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash(format!("global:{}", name).as_bytes()).to_bytes()[..8]);
+        discriminator
     }
+
+    fn read_return_data<T: AnchorDeserialize>(program_id: &Pubkey) -> Result<T> {
+        let (returned_by, data) = get_return_data().ok_or(ErrorCode::InvalidSettings)?;
+        require!(returned_by == *program_id, ErrorCode::Unauthorized);
+        Ok(T::try_from_slice(&data)?)
+    }
+
     #[derive(Accounts)]
     pub struct GetFees<'info> {
         /// CHECK: This is safe for CPI
         pub admin: AccountInfo<'info>,
     }
+
+    #[derive(Accounts)]
+    pub struct GetMintFee<'info> {
+        /// CHECK: This is safe for CPI
+        pub admin: AccountInfo<'info>,
+        /// CHECK: Validated by xft-admin; pass the xft-admin program id when no schedule exists
+        pub fee_schedule: AccountInfo<'info>,
+    }
+
+    #[derive(Accounts)]
+    pub struct DepositFee<'info> {
+        /// CHECK: This is safe for CPI
+        pub treasury: AccountInfo<'info>,
+        /// CHECK: This is safe for CPI
+        pub payer: AccountInfo<'info>,
+        /// CHECK: This is safe for CPI
+        pub treasury_token_account: Option<AccountInfo<'info>>,
+        /// CHECK: This is safe for CPI
+        pub payer_token_account: Option<AccountInfo<'info>>,
+        /// CHECK: This is safe for CPI
+        pub token_program: Option<AccountInfo<'info>>,
+        /// CHECK: This is safe for CPI
+        pub system_program: AccountInfo<'info>,
+    }
 }
 
-fn transfer_lamports<'info>(
-    payer: &AccountInfo<'info>,
-    payout_address: &AccountInfo<'info>,
-    total_fee: u64,
-    system_program: &Program<'info, System>,
-) -> Result<()> {
-    let cpi_accounts = system_program::Transfer {
-        from: payer.clone(),
-        to: payout_address.clone(),
-    };
-    let cpi_ctx = CpiContext::new(system_program.to_account_info(), cpi_accounts);
-    system_program::transfer(cpi_ctx, total_fee)
+#[event]
+pub struct LabelRenewed {
+    pub xft_id: u64,
+    pub payer: Pubkey,
+    pub years: u64,
+    pub fee_paid: u64,
+    pub previous_expire: u64,
+    pub registration_expire: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LabelReclaimed {
+    pub xft_id: u64,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub registration_expire: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct XftRecalled {
+    pub xft_id: u64,
+    pub holder: Pubkey,
+    pub label_owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EditionPrinted {
+    pub xft_id: u64,
+    pub edition: u64,
+    pub max_supply: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub vault: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EditionTransferred {
+    pub xft_id: u64,
+    pub edition: u64,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EditionBurned {
+    pub xft_id: u64,
+    pub edition: u64,
+    pub holder: Pubkey,
+    /// True when the last edition of the run was burned and the master XftAccount closed
+    pub closed: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataUpdated {
+    pub xft_id: u64,
+    pub version: u64,
+    pub previous_ipfs: String,
+    pub ipfs: String,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataFrozen {
+    pub xft_id: u64,
+    pub version: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct XftBurned {
+    pub xft_id: u64,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub remaining_supply: u64,
+    /// True when the last edition was burned and the XftAccount closed
+    pub closed: bool,
+    pub timestamp: i64,
 }
 
 #[event]
@@ -654,4 +3065,32 @@ pub struct WrappedXftEvent {
     pub wrapper: Pubkey,
     pub vault: Pubkey,
     pub timestamp: i64,
+}
+
+#[event]
+pub struct NftWrapped {
+    pub xft_id: u64,
+    pub original_mint: Pubkey,
+    pub metadata_uri: String,
+    pub depositor: Pubkey,
+    pub vault: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NftRedeemed {
+    pub xft_id: u64,
+    pub original_mint: Pubkey,
+    pub holder: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnwrappedXftEvent {
+    pub parent_xft_id: u64,
+    pub wrapped_xft_id: u64,
+    /// Holder that unwrapped
+    pub wrapper: Pubkey,
+    pub vault: Pubkey,
+    pub timestamp: i64,
 }
\ No newline at end of file
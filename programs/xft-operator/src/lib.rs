@@ -3,6 +3,7 @@
 use anchor_lang::prelude::*;
 use anchor_lang::{AccountSerialize, AccountDeserialize};
 use std::str::FromStr;
+use xft_types::{active_operator_role, OperatorEntry, MAX_OPERATOR_SETTINGS};
 
 declare_id!("CvEyB4XdT5nBiGfCK1vW8eSuuAW7o9EZ8v7dFwafZ6P3");
 declare_program!(minter);
@@ -41,29 +42,19 @@ pub mod operator {
         let mut allowed = is_owner;
         if !allowed {
             // Check if authority is already an operator with role = 1 (super operator)
-            let mut is_op_accounts = IsOperator {
-                operator_account: ctx.accounts.operator_account.to_account_info(),
-            };
-            let is_op_ctx = Context::new(
-                ctx.program_id,
-                &mut is_op_accounts,
-                &[],
-                Default::default(),
-            );
-            let (is_op, _license, role, _next_withdraw, _max_withdraw) = operator::is_operator(
-                is_op_ctx,
-                ctx.accounts.authority.key(),
-                xft_id,
-            )?;
-            allowed = is_op && role == 1;
+            let now = Clock::get()?.unix_timestamp as u64;
+            allowed = ctx.accounts.authority_operator_account.as_ref().is_some_and(|entry| {
+                active_operator_role(entry, &ctx.accounts.authority.key(), xft_id, now) == Some(1)
+            });
             if !allowed {
                 allowed = is_operator_fallback(ctx.accounts.authority.key(), xft_id)?;
             }
         }
         require!(allowed, OperatorError::NotAuthorized);
+        require!(settings.len() <= MAX_OPERATOR_SETTINGS, OperatorError::TooManySettings);
         // Store the operator mapping (operator -> xft_id)
         let _operator_account = OperatorAccount::try_from_init(
-            &mut ctx.accounts.operator_account,
+            &mut ctx.accounts.operator_account.to_account_info(),
             &operator,
             xft_id,
             settings.clone(),
@@ -110,21 +101,10 @@ pub mod operator {
         let mut allowed = is_owner;
         if !allowed {
             // Check if authority is already an operator with role = 1 (super operator)
-            let mut is_op_accounts = IsOperator {
-                operator_account: ctx.accounts.operator_account.to_account_info(),
-            };
-            let is_op_ctx = Context::new(
-                ctx.program_id,
-                &mut is_op_accounts,
-                &[],
-                Default::default(),
-            );
-            let (is_op, _license, role, _next_withdraw, _max_withdraw) = operator::is_operator(
-                is_op_ctx,
-                ctx.accounts.authority.key(),
-                xft_id,
-            )?;
-            allowed = is_op && role == 1;
+            let now = Clock::get()?.unix_timestamp as u64;
+            allowed = ctx.accounts.authority_operator_account.as_ref().is_some_and(|entry| {
+                active_operator_role(entry, &ctx.accounts.authority.key(), xft_id, now) == Some(1)
+            });
             if !allowed {
                 allowed = is_operator_fallback(ctx.accounts.authority.key(), xft_id)?;
             }
@@ -165,21 +145,10 @@ pub mod operator {
         let mut allowed = is_owner;
         if !allowed {
             // Check if authority is already an operator with role = 1 (super operator)
-            let mut is_op_accounts = IsOperator {
-                operator_account: ctx.accounts.operator_account.to_account_info(),
-            };
-            let is_op_ctx = Context::new(
-                ctx.program_id,
-                &mut is_op_accounts,
-                &[],
-                Default::default(),
-            );
-            let (is_op, _license, role, _next_withdraw, _max_withdraw) = operator::is_operator(
-                is_op_ctx,
-                ctx.accounts.authority.key(),
-                xft_id,
-            )?;
-            allowed = is_op && role == 1;
+            let now = Clock::get()?.unix_timestamp as u64;
+            allowed = ctx.accounts.authority_operator_account.as_ref().is_some_and(|entry| {
+                active_operator_role(entry, &ctx.accounts.authority.key(), xft_id, now) == Some(1)
+            });
             if !allowed {
                 allowed = is_operator_fallback(ctx.accounts.authority.key(), xft_id)?;
             }
@@ -259,17 +228,28 @@ pub struct ReceiveCpiFromMinter {}
 pub struct CpiToMinter {}
 
 #[derive(Accounts)]
+#[instruction(operator: Pubkey, xft_id: u64)]
 pub struct AddOperator<'info> {
     /// The user attempting to add an operator (must be label owner or operator)
-    #[account(signer)]
+    #[account(mut, signer)]
     pub authority: Signer<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    /// CHECK: xft-minter XftAccount PDA for xft_id, ownership verified by minter::is_label_owner
+    #[account(seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump, seeds::program = minter::ID)]
     pub label_account: AccountInfo<'info>,
     /// The xft-minter program for CPI
     pub xft_minter_program: Program<'info, Minter>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
-    #[account(mut)]
-    pub operator_account: AccountInfo<'info>,
+    /// CHECK: operator entry of `operator` for xft_id, written as an OperatorEntry in add_operator
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OperatorEntry::LEN,
+        seeds = [b"operator", operator.as_ref(), xft_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub operator_account: UncheckedAccount<'info>,
+    /// CHECK: operator entry of authority for xft_id, checked in active_operator_role; omit for the label owner
+    pub authority_operator_account: Option<AccountInfo<'info>>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -290,33 +270,43 @@ pub struct UpdateNextWithdraw<'info> {
 }
 
 #[derive(Accounts)]
+#[instruction(operator: Pubkey, xft_id: u64)]
 pub struct RemoveOperator<'info> {
     #[account(signer)]
     pub authority: Signer<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    /// CHECK: xft-minter XftAccount PDA for xft_id, ownership verified by minter::is_label_owner
+    #[account(seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump, seeds::program = minter::ID)]
     pub label_account: AccountInfo<'info>,
     pub xft_minter_program: Program<'info, Minter>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
-    #[account(mut)]
+    /// CHECK: operator entry of `operator` for xft_id, read in OperatorAccount::fetch
+    #[account(mut, seeds = [b"operator", operator.as_ref(), xft_id.to_le_bytes().as_ref()], bump)]
     pub operator_account: AccountInfo<'info>,
+    /// CHECK: operator entry of authority for xft_id, checked in active_operator_role; omit for the label owner
+    pub authority_operator_account: Option<AccountInfo<'info>>,
 }
 
 #[derive(Accounts)]
+#[instruction(operator: Pubkey, xft_id: u64)]
 pub struct EditWithdrawSettings<'info> {
     #[account(signer)]
     pub authority: Signer<'info>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
+    /// CHECK: xft-minter XftAccount PDA for xft_id, ownership verified by minter::is_label_owner
+    #[account(seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump, seeds::program = minter::ID)]
     pub label_account: AccountInfo<'info>,
     pub xft_minter_program: Program<'info, Minter>,
-    /// CHECK: This is safe for prototyping; actual checks should be implemented in production
-    #[account(mut)]
+    /// CHECK: operator entry of `operator` for xft_id, read in OperatorAccount::fetch
+    #[account(mut, seeds = [b"operator", operator.as_ref(), xft_id.to_le_bytes().as_ref()], bump)]
     pub operator_account: AccountInfo<'info>,
+    /// CHECK: operator entry of authority for xft_id, checked in active_operator_role; omit for the label owner
+    pub authority_operator_account: Option<AccountInfo<'info>>,
 }
 
 #[error_code]
 pub enum OperatorError {
     #[msg("Not authorized to add operator for this xft_id")] 
     NotAuthorized,
+    #[msg("Operator settings have at most 6 entries")]
+    TooManySettings,
 }
 
 #[event]
@@ -0,0 +1,452 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHookAccount;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::state::Account as SplTokenAccount;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_discriminator::SplDiscriminate;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+use xft_types::{active_operator_role, xft_account_address, TransferRule, XftSettings, OPERATOR_PROGRAM_ID};
+
+declare_id!("6LPXsdNeT9MJhFXwHkmH88rYgcdmz8tuVpGZLFgrntWz");
+
+const MINTER_PROGRAM_ID: Pubkey = pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+const MARKET_PROGRAM_ID: Pubkey = pubkey!("JDmExoWsKJe7eMEcxaNKgBe1dgHXe5ns3wGqxgc7kAez");
+
+// Extra accounts resolved by Token-2022 after the 5 transfer-hook accounts
+const EXTRA_ACCOUNTS: usize = 4;
+const OWNER_INDEX: u8 = 3;
+const OPERATOR_PROGRAM_INDEX: u8 = 7;
+
+#[program]
+pub mod xft_transfer_hook {
+    use super::*;
+
+    /// Writes the extra-account-metas Token-2022 passes to transfer_hook for a restricted
+    /// XFT mint: the item's XftAccount, its label XftAccount, the operator program and the
+    /// sender's operator PDA for the label. Anyone can call it once per mint; xft-minter calls
+    /// it when it creates a restricted mint. `edition` selects the mint of printed edition
+    /// `edition` of xft_id, 0 the XFT's own mint.
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+        xft_id: u64,
+        edition: u64,
+    ) -> Result<()> {
+        let (expected_mint, _) = if edition == 0 {
+            Pubkey::find_program_address(&[b"xft_mint", xft_id.to_le_bytes().as_ref()], &MINTER_PROGRAM_ID)
+        } else {
+            Pubkey::find_program_address(
+                &[b"edition_mint", xft_id.to_le_bytes().as_ref(), edition.to_le_bytes().as_ref()],
+                &MINTER_PROGRAM_ID,
+            )
+        };
+        require!(ctx.accounts.mint.key() == expected_mint, HookError::InvalidMint);
+        let xft = XftAccount::load(&ctx.accounts.xft_account)?;
+        require!(
+            xft.xft_id == xft_id
+                && xft_account_address(&MINTER_PROGRAM_ID, xft_id, xft.bump) == Some(ctx.accounts.xft_account.key()),
+            HookError::InvalidXftAccount
+        );
+        let settings = xft.xft_settings()?;
+        require!(settings.label_id > 0, HookError::InvalidXftAccount);
+        let label = XftAccount::load(&ctx.accounts.label_account)?;
+        require!(
+            label.xft_id == settings.label_id
+                && xft_account_address(&MINTER_PROGRAM_ID, label.xft_id, label.bump)
+                    == Some(ctx.accounts.label_account.key()),
+            HookError::InvalidXftAccount
+        );
+
+        let metas = extra_account_metas(
+            ctx.accounts.xft_account.key(),
+            ctx.accounts.label_account.key(),
+            settings.label_id,
+        )?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &metas,
+        )?;
+        Ok(())
+    }
+
+    /// Applies the settings[9] == 0 rule on every transfer: only the label creator, the label
+    /// owner or an active operator of the label may send. The minter's mint authority PDA,
+    /// the permanent delegate used by recall_xft, and its wrap authority PDA, returning
+    /// escrowed parents in unwrap_xft, are also allowed, as is the market escrow authority
+    /// releasing a sold or cancelled listing.
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+        assert_is_transferring(&ctx.accounts.source_token.to_account_info())?;
+        let settings = XftAccount::load(&ctx.accounts.xft_account)?.xft_settings()?;
+        if settings.transfer_rule != TransferRule::Restricted {
+            return Ok(());
+        }
+        let owner = ctx.accounts.owner.key();
+        let (mint_authority, _) = Pubkey::find_program_address(&[b"mint_authority"], &MINTER_PROGRAM_ID);
+        let (wrap_authority, _) = Pubkey::find_program_address(&[b"wrap_authority"], &MINTER_PROGRAM_ID);
+        let (escrow_authority, _) = Pubkey::find_program_address(&[b"escrow_authority"], &MARKET_PROGRAM_ID);
+        if owner == mint_authority || owner == wrap_authority || owner == escrow_authority {
+            return Ok(());
+        }
+        let label = XftAccount::load(&ctx.accounts.label_account)?;
+        if label.addresses.first() == Some(&owner) || label.addresses.get(1) == Some(&owner) {
+            return Ok(());
+        }
+        require!(
+            active_operator_role(&ctx.accounts.operator_account, &owner, settings.label_id, Clock::get()?.unix_timestamp as u64)
+                .is_some(),
+            HookError::TransferRestricted
+        );
+        Ok(())
+    }
+}
+
+fn extra_account_metas(xft_account: Pubkey, label_account: Pubkey, label_id: u64) -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(&xft_account, false, false)?,
+        ExtraAccountMeta::new_with_pubkey(&label_account, false, false)?,
+        ExtraAccountMeta::new_with_pubkey(&OPERATOR_PROGRAM_ID, false, false)?,
+        // Operator PDA [b"operator", owner, label_id] under the operator program
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            OPERATOR_PROGRAM_INDEX,
+            &[
+                Seed::Literal { bytes: b"operator".to_vec() },
+                Seed::AccountKey { index: OWNER_INDEX },
+                Seed::Literal { bytes: label_id.to_le_bytes().to_vec() },
+            ],
+            false,
+            false,
+        )?,
+    ])
+}
+
+// The hook must only run inside a Token-2022 transfer of this mint
+fn assert_is_transferring(source_token: &AccountInfo) -> Result<()> {
+    let data = source_token.try_borrow_data()?;
+    let account = StateWithExtensions::<SplTokenAccount>::unpack(&data)?;
+    let extension = account.get_extension::<TransferHookAccount>()?;
+    require!(bool::from(extension.transferring), HookError::NotTransferring);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: TLV extra-account-meta list, written in initialize_extra_account_meta_list
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(EXTRA_ACCOUNTS).unwrap(),
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: minter XftAccount PDA for xft_id, checked in initialize_extra_account_meta_list
+    pub xft_account: UncheckedAccount<'info>,
+    /// CHECK: minter XftAccount PDA of the item's label, checked in initialize_extra_account_meta_list
+    pub label_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Account order is fixed by the transfer-hook interface, extras follow the meta list
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: source token owner or delegate
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: extra-account-meta list for the mint
+    #[account(seeds = [b"extra-account-metas", mint.key().as_ref()], bump)]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    /// CHECK: item XftAccount, fixed by the meta list
+    pub xft_account: UncheckedAccount<'info>,
+    /// CHECK: label XftAccount, fixed by the meta list
+    pub label_account: UncheckedAccount<'info>,
+    /// CHECK: operator program, fixed by the meta list
+    pub operator_program: UncheckedAccount<'info>,
+    /// CHECK: sender's operator PDA for the label, checked in active_operator_role
+    pub operator_account: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum HookError {
+    #[msg("Mint is not the XFT or edition mint for this xft_id")]
+    InvalidMint,
+    #[msg("Invalid XFT account")]
+    InvalidXftAccount,
+    #[msg("Transfer hook called outside of a transfer")]
+    NotTransferring,
+    #[msg("Only the label creator, owner or an operator can send this XFT")]
+    TransferRestricted,
+}
+
+// Local mirror of minter::XftAccount
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct XftAccount {
+    pub xft_id: u64,
+    pub settings: Vec<u64>,
+    pub addresses: Vec<Pubkey>,
+    pub ipfs: String,
+    pub bump: u8,
+}
+
+impl XftAccount {
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        require!(*account.owner == MINTER_PROGRAM_ID && account.data_len() > 8, HookError::InvalidXftAccount);
+        let data = account.try_borrow_data()?;
+        XftAccount::deserialize(&mut &data[8..]).map_err(|_| error!(HookError::InvalidXftAccount))
+    }
+
+    pub fn xft_settings(&self) -> Result<XftSettings> {
+        XftSettings::decode(&self.settings)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::entrypoint::ProgramResult;
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+    use anchor_lang::solana_program::program_error::ProgramError;
+    use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use anchor_spl::token_2022::spl_token_2022::{
+        self,
+        extension::{transfer_hook::TransferHook as TransferHookMint, BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut},
+        instruction::TokenInstruction,
+        processor::Processor,
+        state::{Account as TokenAccountState, AccountState, Mint as MintState},
+    };
+    use anchor_spl::token_interface::TransferChecked;
+    use spl_transfer_hook_interface::{error::TransferHookError, get_extra_account_metas_address, instruction::execute};
+    use xft_types::{operator_account_address, transfer_checked_with_hook, OperatorEntry};
+
+    const LABEL_ID: u64 = 1;
+    const ITEM_ID: u64 = 2;
+    const NOW: u64 = 1_700_000_000;
+    const LABEL_CREATOR: Pubkey = pubkey!("Creator111111111111111111111111111111111111");
+    const LABEL_OWNER: Pubkey = pubkey!("LabeLowner111111111111111111111111111111111");
+
+    // Stands in for the runtime: dispatches the Token-2022 CPI made by transfer_checked_with_hook.
+    // Off chain Token-2022's own CPI into the hook is a no-op, so the hook is run here with the
+    // accounts Token-2022 resolves for it, while the source and destination are marked transferring.
+    struct TestRuntime;
+
+    impl SyscallStubs for TestRuntime {
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            let accounts = instruction
+                .accounts
+                .iter()
+                .map(|meta| account_infos.iter().find(|info| *info.key == meta.pubkey).cloned())
+                .collect::<Option<Vec<_>>>()
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            if instruction.program_id != spl_token_2022::id() {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            process_token_2022(instruction, &accounts)
+        }
+
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            let clock = Clock { unix_timestamp: NOW as i64, ..Clock::default() };
+            unsafe { *(var_addr as *mut Clock) = clock };
+            0
+        }
+    }
+
+    fn process_token_2022(instruction: &Instruction, accounts: &[AccountInfo]) -> ProgramResult {
+        let TokenInstruction::TransferChecked { amount, .. } = TokenInstruction::unpack(&instruction.data)? else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        let (source, mint, destination, authority) = (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+        // The hook program can only be called when its account came with the transfer
+        if !accounts.iter().any(|info| *info.key == crate::ID) {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        set_transferring(source, true)?;
+        set_transferring(destination, true)?;
+        let result = execute_hook(source, mint, destination, authority, &accounts[4..], amount);
+        set_transferring(source, false)?;
+        set_transferring(destination, false)?;
+        result?;
+        Processor::process(&instruction.program_id, accounts, &instruction.data)
+    }
+
+    // Same account resolution as spl_transfer_hook_interface::onchain::invoke_execute
+    fn execute_hook<'a>(
+        source: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        additional_accounts: &[AccountInfo<'a>],
+        amount: u64,
+    ) -> ProgramResult {
+        let mut instruction = execute(&crate::ID, source.key, mint.key, destination.key, authority.key, amount);
+        let mut infos = vec![source.clone(), mint.clone(), destination.clone(), authority.clone()];
+        let validation = get_extra_account_metas_address(mint.key, &crate::ID);
+        if let Some(validation_info) = additional_accounts.iter().find(|info| *info.key == validation) {
+            instruction.accounts.push(AccountMeta::new_readonly(validation, false));
+            infos.push(validation_info.clone());
+            ExtraAccountMetaList::add_to_cpi_instruction::<ExecuteInstruction>(
+                &mut instruction,
+                &mut infos,
+                &validation_info.try_borrow_data()?,
+                additional_accounts,
+            )?;
+        }
+        let infos: &'a [AccountInfo<'a>] = Box::leak(infos.into_boxed_slice());
+        entry(&crate::ID, infos, &instruction.data)
+    }
+
+    fn set_transferring(token_account: &AccountInfo, transferring: bool) -> ProgramResult {
+        let mut data = token_account.try_borrow_mut_data()?;
+        let mut state = StateWithExtensionsMut::<TokenAccountState>::unpack(&mut data)?;
+        state.get_extension_mut::<TransferHookAccount>()?.transferring = transferring.into();
+        Ok(())
+    }
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+        is_signer: bool,
+        executable: bool,
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+            TestAccount { key, owner, lamports: 1_000_000_000, data, is_signer: false, executable: false }
+        }
+
+        fn program(key: Pubkey) -> Self {
+            TestAccount { executable: true, ..Self::new(key, Pubkey::default(), vec![]) }
+        }
+
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                self.is_signer,
+                !self.executable,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                self.executable,
+                0,
+            )
+        }
+    }
+
+    fn xft_account(xft_id: u64, settings: Vec<u64>, addresses: Vec<Pubkey>) -> TestAccount {
+        let (key, bump) = Pubkey::find_program_address(&[b"xft", xft_id.to_le_bytes().as_ref()], &MINTER_PROGRAM_ID);
+        let xft = XftAccount { xft_id, settings, addresses, ipfs: String::new(), bump };
+        let mut data = vec![0; 8];
+        xft.serialize(&mut data).unwrap();
+        TestAccount::new(key, MINTER_PROGRAM_ID, data)
+    }
+
+    fn hook_mint(key: Pubkey) -> TestAccount {
+        let len = ExtensionType::try_calculate_account_len::<MintState>(&[ExtensionType::TransferHook]).unwrap();
+        let mut data = vec![0; len];
+        let mut state = StateWithExtensionsMut::<MintState>::unpack_uninitialized(&mut data).unwrap();
+        state.init_extension::<TransferHookMint>(true).unwrap().program_id = Some(crate::ID).try_into().unwrap();
+        state.base = MintState { supply: 1, decimals: 0, is_initialized: true, ..MintState::default() };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        TestAccount::new(key, spl_token_2022::id(), data)
+    }
+
+    fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> TestAccount {
+        let len = ExtensionType::try_calculate_account_len::<TokenAccountState>(&[ExtensionType::TransferHookAccount])
+            .unwrap();
+        let mut data = vec![0; len];
+        let mut state = StateWithExtensionsMut::<TokenAccountState>::unpack_uninitialized(&mut data).unwrap();
+        state.init_extension::<TransferHookAccount>(true).unwrap();
+        state.base = TokenAccountState { mint, owner, amount, state: AccountState::Initialized, ..TokenAccountState::default() };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        TestAccount::new(Pubkey::new_unique(), spl_token_2022::id(), data)
+    }
+
+    fn token_amount(info: &AccountInfo) -> u64 {
+        let data = info.try_borrow_data().unwrap();
+        StateWithExtensions::<TokenAccountState>::unpack(&data).unwrap().base.amount
+    }
+
+    /// Sends a restricted item of label LABEL_ID from `sender` through transfer_checked_with_hook,
+    /// `operator_expiry` makes `sender` an operator of the label until then. Returns the
+    /// source and destination balances afterwards.
+    fn send_restricted(sender: Pubkey, operator_expiry: Option<u64>, pass_hook_accounts: bool) -> std::result::Result<(u64, u64), ProgramError> {
+        set_syscall_stubs(Box::new(TestRuntime));
+        let label = xft_account(LABEL_ID, vec![0, 1, 0, 1, 0, 0, 1, NOW + 1, 0, 1], vec![LABEL_CREATOR, LABEL_OWNER]);
+        let item = xft_account(ITEM_ID, vec![LABEL_ID, 0, 0, 7, 0, 0, 1, 0, 0, 0], vec![LABEL_CREATOR, LABEL_OWNER]);
+        let (mint, _) = Pubkey::find_program_address(&[b"xft_mint", ITEM_ID.to_le_bytes().as_ref()], &MINTER_PROGRAM_ID);
+        let metas = extra_account_metas(item.key, label.key, LABEL_ID).unwrap();
+        let mut meta_list = vec![0; ExtraAccountMetaList::size_of(EXTRA_ACCOUNTS).unwrap()];
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut meta_list, &metas).unwrap();
+        let operator_key = operator_account_address(&sender, LABEL_ID);
+        let operator_account = match operator_expiry {
+            Some(expiry) => {
+                let entry = OperatorEntry { settings: vec![0, expiry, 0, 0, 0, 0] };
+                TestAccount::new(operator_key, OPERATOR_PROGRAM_ID, entry.try_to_vec().unwrap())
+            }
+            None => TestAccount::new(operator_key, Pubkey::default(), vec![]),
+        };
+        let mut authority = TestAccount::new(sender, Pubkey::default(), vec![]);
+        authority.is_signer = true;
+
+        let mut accounts = vec![
+            token_account(mint, sender, 1),
+            hook_mint(mint),
+            token_account(mint, Pubkey::new_unique(), 0),
+            authority,
+            TestAccount::program(spl_token_2022::id()),
+            TestAccount::new(get_extra_account_metas_address(&mint, &crate::ID), crate::ID, meta_list),
+            item,
+            label,
+            TestAccount::program(OPERATOR_PROGRAM_ID),
+            operator_account,
+            TestAccount::program(crate::ID),
+        ];
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        let hook_accounts = if pass_hook_accounts { infos[5..].to_vec() } else { vec![] };
+        let ctx = CpiContext::new(
+            infos[4].clone(),
+            TransferChecked {
+                from: infos[0].clone(),
+                mint: infos[1].clone(),
+                to: infos[2].clone(),
+                authority: infos[3].clone(),
+            },
+        )
+        .with_remaining_accounts(hook_accounts);
+        transfer_checked_with_hook(ctx, 1, 0).map_err(ProgramError::from)?;
+        Ok((token_amount(&infos[0]), token_amount(&infos[2])))
+    }
+
+    fn restricted() -> ProgramError {
+        ProgramError::Custom(HookError::TransferRestricted.into())
+    }
+
+    #[test]
+    fn restricted_transfer_runs_the_hook() {
+        assert_eq!(send_restricted(LABEL_OWNER, None, true), Ok((0, 1)));
+        assert_eq!(send_restricted(LABEL_CREATOR, None, true), Ok((0, 1)));
+        assert_eq!(send_restricted(Pubkey::new_unique(), None, true), Err(restricted()));
+        // Active label operators may send, expired ones may not
+        assert_eq!(send_restricted(Pubkey::new_unique(), Some(NOW + 1), true), Ok((0, 1)));
+        assert_eq!(send_restricted(Pubkey::new_unique(), Some(NOW), true), Err(restricted()));
+        // Without the hook accounts the hook cannot be run, so the transfer is never made
+        assert_eq!(send_restricted(LABEL_OWNER, None, false), Err(TransferHookError::IncorrectAccount.into()));
+    }
+}
@@ -0,0 +1,432 @@
+// Shared XFT types for minter, market and vault. Types only: no entrypoint, no
+// instructions, so programs can depend on it without allocator conflicts.
+
+use anchor_lang::prelude::*;
+
+pub mod operator;
+pub mod title;
+pub mod token;
+pub use operator::*;
+pub use title::*;
+pub use token::*;
+
+/// Registration terms are counted in 365 day years
+pub const SECONDS_PER_YEAR: u64 = 365 * 86400;
+
+/// After a lead or profile label expires only the previous owner can renew it for this long,
+/// after that the label can be reclaimed through the xft-market reclaim auction
+pub const GRACE_PERIOD: u64 = 30 * 86400;
+
+/// Seed prefix of the minter's XftAccount PDAs, `[XFT_SEED, xft_id.to_le_bytes()]`
+pub const XFT_SEED: &[u8] = b"xft";
+
+/// Address of the minter XftAccount PDA for `xft_id` with its stored bump, None when the
+/// seeds are not a valid PDA
+pub fn xft_account_address(minter_program_id: &Pubkey, xft_id: u64, bump: u8) -> Option<Pubkey> {
+    Pubkey::create_program_address(&[XFT_SEED, xft_id.to_le_bytes().as_ref(), &[bump]], minter_program_id).ok()
+}
+
+#[error_code(offset = 9000)]
+pub enum XftSettingsError {
+    #[msg("Unknown label type")]
+    InvalidLabelType,
+    #[msg("Invalid settings for label type")]
+    InvalidSettings,
+    #[msg("Title is empty or has characters outside a-z, 0-9, '-' and '_'")]
+    InvalidTitle,
+}
+
+/// Label Types (settings[3]):
+/// * 1: Lead Label (1 of 1)
+/// * 2: Profile Label (1 of 1)
+/// * 3: Tag Label (must be limited edition)
+/// * 4: Chapter Label (must be limited edition)
+/// * 5: Operator License (must be limited edition)
+/// * 6: Marketplace License (must be limited edition)
+/// * 7: Art/tickets/gaming (can be 1 of 1 or limited edition)
+/// * 8: wrappedTo (1 of 1)
+/// * 9: open
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LabelType {
+    Lead,
+    Profile,
+    Tag,
+    Chapter,
+    OperatorLicense,
+    MarketplaceLicense,
+    Art,
+    WrappedTo,
+    Open,
+}
+
+impl LabelType {
+    pub fn from_u64(value: u64) -> Result<Self> {
+        Ok(match value {
+            1 => LabelType::Lead,
+            2 => LabelType::Profile,
+            3 => LabelType::Tag,
+            4 => LabelType::Chapter,
+            5 => LabelType::OperatorLicense,
+            6 => LabelType::MarketplaceLicense,
+            7 => LabelType::Art,
+            8 => LabelType::WrappedTo,
+            9 => LabelType::Open,
+            _ => return Err(XftSettingsError::InvalidLabelType.into()),
+        })
+    }
+
+    pub fn as_u64(self) -> u64 {
+        match self {
+            LabelType::Lead => 1,
+            LabelType::Profile => 2,
+            LabelType::Tag => 3,
+            LabelType::Chapter => 4,
+            LabelType::OperatorLicense => 5,
+            LabelType::MarketplaceLicense => 6,
+            LabelType::Art => 7,
+            LabelType::WrappedTo => 8,
+            LabelType::Open => 9,
+        }
+    }
+
+    /// Labels pay registration fees, expire and reserve their title; items (7, 8) do not
+    pub fn is_label(self) -> bool {
+        !matches!(self, LabelType::Art | LabelType::WrappedTo)
+    }
+
+    pub fn is_license(self) -> bool {
+        matches!(self, LabelType::OperatorLicense | LabelType::MarketplaceLicense)
+    }
+
+    /// Lead and profile labels go through the grace period and reclaim auction on expiry
+    pub fn is_reclaimable(self) -> bool {
+        matches!(self, LabelType::Lead | LabelType::Profile)
+    }
+
+    pub fn is_limited_edition_only(self) -> bool {
+        matches!(
+            self,
+            LabelType::Tag | LabelType::Chapter | LabelType::OperatorLicense | LabelType::MarketplaceLicense
+        )
+    }
+}
+
+/// Transfer rule (settings[9]):
+/// * 0: restricted, only the label creator, owner or operators can send it
+/// * 1: freely transferable
+/// * 2: soulbound, never transferable (Token-2022 non-transferable mint)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferRule {
+    Restricted,
+    Transferable,
+    Soulbound,
+}
+
+impl TransferRule {
+    pub fn from_u64(value: u64) -> Result<Self> {
+        Ok(match value {
+            0 => TransferRule::Restricted,
+            1 => TransferRule::Transferable,
+            2 => TransferRule::Soulbound,
+            _ => return Err(XftSettingsError::InvalidSettings.into()),
+        })
+    }
+
+    pub fn as_u64(self) -> u64 {
+        match self {
+            TransferRule::Restricted => 0,
+            TransferRule::Transferable => 1,
+            TransferRule::Soulbound => 2,
+        }
+    }
+}
+
+/// Typed view of `XftAccount.settings`. Accounts keep the positional Vec<u64> on chain:
+/// * 0: link to label
+/// * 1: registration in years
+/// * 2: operator license
+/// * 3: xft type
+/// * 4: if type is license, license term (expiry, set on chain with settings[7])
+/// * 5: 0 false, 1 true (formerly mint pass)
+/// * 6: quantity
+/// * 7: label registration expire, set on chain from settings[1] paid years
+/// * 8: if type is market license, marketplace fee percentage
+/// * 9: transfer rule, see TransferRule
+/// * 10: wrapto
+/// * 11: label split for marketplace license, wrapped child id after wrap_xft (0 again after unwrap_xft)
+/// * 12: label vault locked
+/// * 13: label vault unlock date
+/// * 14..: children listed on the market under this label, 0 = free slot
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XftSettings {
+    pub label_id: u64,
+    pub registration_years: u64,
+    pub operator_license: u64,
+    pub label_type: LabelType,
+    pub license_term: u64,
+    pub mint_pass: bool,
+    pub quantity: u64,
+    pub registration_expire: u64,
+    pub marketplace_fee: u64,
+    pub transfer_rule: TransferRule,
+    pub wrap_to: u64,
+    pub label_split: u64,
+    pub vault_locked: bool,
+    pub vault_unlock_date: u64,
+    pub children: Vec<u64>,
+}
+
+impl XftSettings {
+    pub const CHILDREN_START: usize = 14;
+
+    /// Decodes a vector-encoded settings array. Older accounts may have a shorter
+    /// vector, missing slots read as 0.
+    pub fn decode(settings: &[u64]) -> Result<Self> {
+        let at = |i: usize| settings.get(i).copied().unwrap_or(0);
+        Ok(XftSettings {
+            label_id: at(0),
+            registration_years: at(1),
+            operator_license: at(2),
+            label_type: LabelType::from_u64(at(3))?,
+            license_term: at(4),
+            mint_pass: at(5) != 0,
+            quantity: at(6),
+            registration_expire: at(7),
+            marketplace_fee: at(8),
+            transfer_rule: TransferRule::from_u64(at(9))?,
+            wrap_to: at(10),
+            label_split: at(11),
+            vault_locked: at(12) != 0,
+            vault_unlock_date: at(13),
+            children: settings.get(Self::CHILDREN_START..).map(|c| c.to_vec()).unwrap_or_default(),
+        })
+    }
+
+    /// Decodes caller-supplied settings for a new mint and applies the label type rules
+    pub fn new(settings: &[u64]) -> Result<Self> {
+        let decoded = Self::decode(settings)?;
+        decoded.validate()?;
+        Ok(decoded)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        // Lead labels are 1 of 1
+        require!(
+            self.label_type != LabelType::Lead || self.quantity == 1,
+            XftSettingsError::InvalidSettings
+        );
+        // Tag, Chapter, Operator License and Marketplace License must be limited editions
+        require!(
+            !self.label_type.is_limited_edition_only() || self.quantity > 1,
+            XftSettingsError::InvalidSettings
+        );
+        // Every label needs a registration term, items do not expire
+        require!(
+            !self.label_type.is_label() || self.registration_years > 0,
+            XftSettingsError::InvalidSettings
+        );
+        Ok(())
+    }
+
+    pub fn encode(&self) -> Vec<u64> {
+        let mut settings = vec![
+            self.label_id,
+            self.registration_years,
+            self.operator_license,
+            self.label_type.as_u64(),
+            self.license_term,
+            self.mint_pass as u64,
+            self.quantity,
+            self.registration_expire,
+            self.marketplace_fee,
+            self.transfer_rule.as_u64(),
+            self.wrap_to,
+            self.label_split,
+            self.vault_locked as u64,
+            self.vault_unlock_date,
+        ];
+        settings.extend_from_slice(&self.children);
+        settings
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.registration_expire <= now
+    }
+
+    pub fn in_grace_period(&self, now: u64) -> bool {
+        self.is_expired(now) && now < self.registration_expire.saturating_add(GRACE_PERIOD)
+    }
+
+    /// Expired lead or profile label whose grace period has run out
+    pub fn is_reclaimable(&self, now: u64) -> bool {
+        self.label_type.is_reclaimable() && self.is_expired(now) && !self.in_grace_period(now)
+    }
+
+    /// Sets the registration expiry from the paid registration years, discarding any
+    /// caller-supplied expiry or license term. Items (7, 8) do not expire.
+    pub fn start_registration(&mut self, now: u64) {
+        self.registration_expire = if self.label_type.is_label() {
+            now.saturating_add(self.registration_years.saturating_mul(SECONDS_PER_YEAR))
+        } else {
+            0
+        };
+        self.sync_license_term();
+    }
+
+    /// Extends the registration by `years`, counting from now if it has already expired
+    pub fn renew(&mut self, now: u64, years: u64) {
+        let start = self.registration_expire.max(now);
+        self.registration_expire = start.saturating_add(years.saturating_mul(SECONDS_PER_YEAR));
+        self.registration_years = self.registration_years.saturating_add(years);
+        self.sync_license_term();
+    }
+
+    // License terms run with the paid registration, other types have none
+    fn sync_license_term(&mut self) {
+        self.license_term = if self.label_type.is_license() {
+            self.registration_expire
+        } else {
+            0
+        };
+    }
+
+    /// Puts `xft_id` in the first free child slot, appending if all are taken
+    pub fn add_child(&mut self, xft_id: u64) {
+        match self.children.iter_mut().find(|slot| **slot == 0) {
+            Some(slot) => *slot = xft_id,
+            None => self.children.push(xft_id),
+        }
+    }
+
+    /// Frees the child slot holding `xft_id`, returns false if it is not a child
+    pub fn remove_child(&mut self, xft_id: u64) -> bool {
+        match self.children.iter_mut().find(|slot| **slot == xft_id) {
+            Some(slot) => {
+                *slot = 0;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(label_type: u64, years: u64, quantity: u64) -> Vec<u64> {
+        let mut settings = vec![0; XftSettings::CHILDREN_START];
+        settings[1] = years;
+        settings[3] = label_type;
+        settings[6] = quantity;
+        settings[9] = 1;
+        settings
+    }
+
+    #[test]
+    fn encode_round_trips_decode() {
+        let mut raw = settings(4, 2, 10);
+        raw[0] = 7;
+        raw[7] = 1_000;
+        raw[12] = 1;
+        raw.extend_from_slice(&[11, 0, 12]);
+        let decoded = XftSettings::decode(&raw).unwrap();
+        assert_eq!(decoded.label_type, LabelType::Chapter);
+        assert_eq!(decoded.transfer_rule, TransferRule::Transferable);
+        assert!(decoded.vault_locked);
+        assert_eq!(decoded.children, vec![11, 0, 12]);
+        assert_eq!(decoded.encode(), raw);
+    }
+
+    #[test]
+    fn decode_pads_short_settings() {
+        let decoded = XftSettings::decode(&[0, 1, 0, 7]).unwrap();
+        assert_eq!(decoded.label_type, LabelType::Art);
+        assert_eq!(decoded.transfer_rule, TransferRule::Restricted);
+        assert!(decoded.children.is_empty());
+        assert_eq!(decoded.encode().len(), XftSettings::CHILDREN_START);
+    }
+
+    #[test]
+    fn decode_rejects_unknown_types() {
+        assert!(XftSettings::decode(&settings(0, 1, 1)).is_err());
+        assert!(XftSettings::decode(&settings(10, 1, 1)).is_err());
+        let mut raw = settings(7, 0, 1);
+        raw[9] = 3;
+        assert!(XftSettings::decode(&raw).is_err());
+    }
+
+    #[test]
+    fn validate_applies_label_type_rules() {
+        assert!(XftSettings::new(&settings(1, 1, 1)).is_ok());
+        assert!(XftSettings::new(&settings(1, 1, 2)).is_err());
+        assert!(XftSettings::new(&settings(3, 1, 5)).is_ok());
+        assert!(XftSettings::new(&settings(3, 1, 1)).is_err());
+        assert!(XftSettings::new(&settings(7, 0, 1)).is_ok());
+        assert!(XftSettings::new(&settings(8, 0, 1)).is_ok());
+    }
+
+    #[test]
+    fn validate_requires_registration_years_for_labels() {
+        for label_type in [1, 2, 3, 4, 5, 6, 9] {
+            let quantity = if label_type == 1 { 1 } else { 2 };
+            assert!(XftSettings::new(&settings(label_type, 0, quantity)).is_err());
+            assert!(XftSettings::new(&settings(label_type, 1, quantity)).is_ok());
+        }
+    }
+
+    #[test]
+    fn add_child_reuses_free_slots() {
+        let mut decoded = XftSettings::decode(&settings(3, 1, 5)).unwrap();
+        decoded.add_child(11);
+        decoded.add_child(12);
+        assert_eq!(decoded.children, vec![11, 12]);
+        assert!(decoded.remove_child(11));
+        assert!(!decoded.remove_child(13));
+        assert_eq!(decoded.children, vec![0, 12]);
+        decoded.add_child(13);
+        assert_eq!(decoded.children, vec![13, 12]);
+    }
+
+    #[test]
+    fn registration_and_renewal_terms() {
+        let now = 1_000_000;
+        let mut license = XftSettings::decode(&settings(5, 2, 10)).unwrap();
+        license.start_registration(now);
+        assert_eq!(license.registration_expire, now + 2 * SECONDS_PER_YEAR);
+        assert_eq!(license.license_term, license.registration_expire);
+        // Renewing before expiry extends the current term
+        license.renew(now, 1);
+        assert_eq!(license.registration_expire, now + 3 * SECONDS_PER_YEAR);
+        assert_eq!(license.registration_years, 3);
+        assert_eq!(license.license_term, license.registration_expire);
+        // Renewing after expiry counts from now
+        let later = license.registration_expire + 100;
+        license.renew(later, 1);
+        assert_eq!(license.registration_expire, later + SECONDS_PER_YEAR);
+
+        let mut item = XftSettings::decode(&settings(7, 3, 1)).unwrap();
+        item.start_registration(now);
+        assert_eq!(item.registration_expire, 0);
+        assert_eq!(item.license_term, 0);
+    }
+
+    #[test]
+    fn grace_period_and_reclaim() {
+        let mut lead = XftSettings::decode(&settings(1, 1, 1)).unwrap();
+        lead.registration_expire = 1_000;
+        assert!(!lead.is_expired(999));
+        assert!(!lead.in_grace_period(999));
+        assert!(lead.is_expired(1_000));
+        assert!(lead.in_grace_period(1_000));
+        assert!(!lead.is_reclaimable(1_000));
+        assert!(lead.in_grace_period(1_000 + GRACE_PERIOD - 1));
+        assert!(!lead.in_grace_period(1_000 + GRACE_PERIOD));
+        assert!(lead.is_reclaimable(1_000 + GRACE_PERIOD));
+
+        // Only lead and profile labels go to the reclaim auction
+        let mut tag = XftSettings::decode(&settings(3, 1, 5)).unwrap();
+        tag.registration_expire = 1_000;
+        assert!(!tag.is_reclaimable(1_000 + GRACE_PERIOD));
+    }
+}
@@ -0,0 +1,55 @@
+// xft-operator entries. xft-operator writes them, xft-admin, xft-minter and
+// xft-transfer-hook read them through active_operator_role.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+
+/// xft-operator program, owner of every operator entry
+pub const OPERATOR_PROGRAM_ID: Pubkey = pubkey!("CvEyB4XdT5nBiGfCK1vW8eSuuAW7o9EZ8v7dFwafZ6P3");
+
+/// Seed prefix of operator entries, `[OPERATOR_SEED, address, label_id.to_le_bytes()]`
+pub const OPERATOR_SEED: &[u8] = b"operator";
+
+/// Settings slots of an operator entry
+pub const MAX_OPERATOR_SETTINGS: usize = 6;
+
+/// Operator entry of `address` for a label, stored without discriminator
+///
+/// * 0: license
+/// * 1: access expiry (unix timestamp)
+/// * 2: role, 1 = super operator (can add other operators)
+/// * 3: next withdraw date (unix timestamp)
+/// * 4: max SOL withdraw amount
+/// * 5: withdraw frequency in days
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct OperatorEntry {
+    pub settings: Vec<u64>,
+}
+
+impl OperatorEntry {
+    /// Account space for MAX_OPERATOR_SETTINGS settings
+    pub const LEN: usize = 4 + MAX_OPERATOR_SETTINGS * 8;
+}
+
+/// Address of the operator entry of `address` for `label_id`
+pub fn operator_account_address(address: &Pubkey, label_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[OPERATOR_SEED, address.as_ref(), label_id.to_le_bytes().as_ref()],
+        &OPERATOR_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Role (settings[2]) of `address` for `label_id` while its access has not expired at `now`.
+/// None when `operator_account` is not that xft-operator entry, cannot be read or has expired.
+pub fn active_operator_role(operator_account: &AccountInfo, address: &Pubkey, label_id: u64, now: u64) -> Option<u64> {
+    if operator_account.key() != operator_account_address(address, label_id)
+        || *operator_account.owner != OPERATOR_PROGRAM_ID
+    {
+        return None;
+    }
+    let data = operator_account.try_borrow_data().ok()?;
+    let entry = OperatorEntry::deserialize(&mut &data[..]).ok()?;
+    let access_expire = entry.settings.get(1).copied().unwrap_or(0);
+    (access_expire > now).then(|| entry.settings.get(2).copied().unwrap_or(0))
+}
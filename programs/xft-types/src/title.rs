@@ -0,0 +1,147 @@
+// Canonical label titles. Every title check (the minter's title_lookup PDA and the
+// xft-admin title reservations) goes through normalize_title / title_seed so that
+// case variants and look-alike characters map to the same label.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::XftSettingsError;
+
+/// PDA seeds are limited to 32 bytes, longer titles are seeded by their hash
+pub const MAX_SEED_LEN: usize = 32;
+
+/// Look-alike characters mapped to the latin letter they imitate. Applied after NFKC and
+/// lowercasing, so only lowercase forms are listed.
+const CONFUSABLES: &[(char, char)] = &[
+    // Cyrillic
+    ('а', 'a'),
+    ('в', 'b'),
+    ('с', 'c'),
+    ('ԁ', 'd'),
+    ('е', 'e'),
+    ('һ', 'h'),
+    ('н', 'h'),
+    ('і', 'i'),
+    ('ј', 'j'),
+    ('к', 'k'),
+    ('ӏ', 'l'),
+    ('м', 'm'),
+    ('о', 'o'),
+    ('р', 'p'),
+    ('ԛ', 'q'),
+    ('ѕ', 's'),
+    ('т', 't'),
+    ('у', 'y'),
+    ('ԝ', 'w'),
+    ('х', 'x'),
+    // Greek
+    ('α', 'a'),
+    ('β', 'b'),
+    ('ε', 'e'),
+    ('η', 'h'),
+    ('ι', 'i'),
+    ('κ', 'k'),
+    ('μ', 'm'),
+    ('ν', 'v'),
+    ('ο', 'o'),
+    ('ρ', 'p'),
+    ('τ', 't'),
+    ('υ', 'u'),
+    ('χ', 'x'),
+    ('ζ', 'z'),
+    // Latin variants
+    ('ı', 'i'),
+    ('ɑ', 'a'),
+    ('ɡ', 'g'),
+];
+
+fn fold_confusable(c: char) -> char {
+    CONFUSABLES
+        .iter()
+        .find(|(from, _)| *from == c)
+        .map(|(_, to)| *to)
+        .unwrap_or(c)
+}
+
+/// Allowed characters of a normalized title: a-z, 0-9, '-' and '_'
+fn is_allowed(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_'
+}
+
+/// Canonical form of a title: NFKC, lowercased, confusables folded and whitespace removed.
+/// Does not check the character set, see normalize_title.
+pub fn canonical_title(title: &str) -> String {
+    title
+        .nfkc()
+        .flat_map(char::to_lowercase)
+        .map(fold_confusable)
+        .filter(|c| !c.is_whitespace())
+        .collect()
+}
+
+/// Canonical form of a title, failing if it is empty or has characters outside the allowed set
+pub fn normalize_title(title: &str) -> Result<String> {
+    let normalized = canonical_title(title);
+    require!(
+        !normalized.is_empty() && normalized.chars().all(is_allowed),
+        XftSettingsError::InvalidTitle
+    );
+    Ok(normalized)
+}
+
+/// PDA seed for a title: the canonical title, or its sha256 when longer than 32 bytes
+pub fn title_seed(title: &str) -> Vec<u8> {
+    let normalized = canonical_title(title);
+    if normalized.len() <= MAX_SEED_LEN {
+        normalized.into_bytes()
+    } else {
+        hash(normalized.as_bytes()).to_bytes().to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_title_folds_case_and_whitespace() {
+        assert_eq!(canonical_title("Alice"), "alice");
+        assert_eq!(canonical_title(" Al ice\t"), "alice");
+        assert_eq!(canonical_title("ＡＬＩＣＥ"), "alice");
+    }
+
+    #[test]
+    fn canonical_title_folds_confusables() {
+        // Cyrillic а and о, Greek ρ
+        assert_eq!(canonical_title("\u{430}lice"), "alice");
+        assert_eq!(canonical_title("b\u{43e}b"), "bob");
+        assert_eq!(canonical_title("\u{3c1}ay"), "pay");
+    }
+
+    #[test]
+    fn normalize_title_checks_character_set() {
+        assert_eq!(normalize_title("My-Label_1").unwrap(), "my-label_1");
+        assert!(normalize_title("").is_err());
+        assert!(normalize_title("   ").is_err());
+        assert!(normalize_title("al!ce").is_err());
+        assert!(normalize_title("café").is_err());
+    }
+
+    #[test]
+    fn title_seed_matches_variants() {
+        assert_eq!(title_seed("Alice"), b"alice".to_vec());
+        assert_eq!(title_seed("Alice"), title_seed("\u{430}LICE"));
+    }
+
+    #[test]
+    fn title_seed_hashes_long_titles() {
+        let max = "a".repeat(MAX_SEED_LEN);
+        assert_eq!(title_seed(&max), max.as_bytes().to_vec());
+        let long = "a".repeat(MAX_SEED_LEN + 1);
+        let seed = title_seed(&long);
+        assert_eq!(seed.len(), 32);
+        assert_eq!(seed, hash(long.as_bytes()).to_bytes().to_vec());
+        assert_eq!(seed, title_seed(&long.to_uppercase()));
+    }
+}
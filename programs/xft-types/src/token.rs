@@ -0,0 +1,28 @@
+// Token transfers of XFT mints. Restricted XFTs carry a Token-2022 transfer hook, whose
+// extra accounts anchor-spl's transfer_checked does not forward.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::onchain::invoke_transfer_checked;
+use anchor_spl::token_interface::TransferChecked;
+
+/// transfer_checked that also works for transfer hook mints: `ctx.remaining_accounts` must
+/// hold the mint's extra-account-meta list, the hook program and the accounts the list
+/// resolves to, in any order. Mints without a hook ignore them.
+pub fn transfer_checked_with_hook<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    invoke_transfer_checked(
+        ctx.program.key,
+        ctx.accounts.from,
+        ctx.accounts.mint,
+        ctx.accounts.to,
+        ctx.accounts.authority,
+        &ctx.remaining_accounts,
+        amount,
+        decimals,
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_lang::{AnchorSerialize, AnchorDeserialize};
+use anchor_spl::token_interface::TokenAccount;
 declare_id!("6k8vntYQMbU9AUtnMcypeoS8bf1Ncqv5ZQPqrU3DoH5X");
 declare_program!(minter);
 declare_program!(operator);
+declare_program!(admin_xft);
 use minter::program::Minter;
 use operator::program::Operator;
 
@@ -30,7 +32,166 @@ pub mod xft_vault {
         Ok(vault.key())
     }
 
+    /// Creates the vault of printed edition `edition` of xft_id. Only xft-minter::print_edition
+    /// can call this, signing with its vault authority PDA.
+    pub fn create_edition_vault(
+        ctx: Context<CreateEditionVault>,
+        xft_id: u64,
+        edition: u64,
+        xft_type: u64,
+    ) -> Result<Pubkey> {
+        let vault = &mut ctx.accounts.vault;
+        vault.xft_id = xft_id;
+        vault.xft_type = xft_type;
+        vault.unlock_date = 0;
+        emit!(EditionVaultCreated {
+            xft_id,
+            edition,
+            vault: vault.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(vault.key())
+    }
+
+    /// Withdraws SOL from an edition vault. Only the holder of the edition's token (the mint in
+    /// the xft-minter edition record) can withdraw.
+    pub fn withdraw_edition_sol(ctx: Context<WithdrawEditionSol>, xft_id: u64, edition: u64, amount: u64) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.vault_withdrawals, VaultError::WithdrawalsPaused);
+        let record = Edition::load(&ctx.accounts.edition_record)?;
+        require!(
+            record.xft_id == xft_id && record.number == edition,
+            VaultError::Unauthorized
+        );
+        let holder = &ctx.accounts.edition_token_account;
+        require!(
+            holder.mint == record.mint && holder.owner == ctx.accounts.authority.key() && holder.amount == 1,
+            VaultError::Unauthorized
+        );
+        require!(ctx.accounts.vault.xft_id == xft_id, VaultError::Unauthorized);
+        require!(ctx.accounts.vault.unlock_date < Clock::get()?.unix_timestamp, VaultError::WithdrawTooSoon);
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let rent = Rent::get()?.minimum_balance(vault_info.data_len());
+        require!(vault_info.lamports().saturating_sub(rent) >= amount, VaultError::WithdrawTooMuch);
+        **vault_info.try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.authority.to_account_info().try_borrow_mut_lamports()? += amount;
+        Ok(())
+    }
+
+    /// Hands a reclaimed label's vault to its new owner. Only xft-minter::reclaim_label can
+    /// call this, signing with its vault authority PDA. The SOL already in the vault stays
+    /// claimable by the previous owner through claim_previous_contents. Contents still
+    /// unclaimed from an earlier handover are paid out to that owner first.
+    pub fn hand_over_vault(ctx: Context<HandOverVault>, xft_id: u64, previous_owner: Pubkey) -> Result<()> {
+        require!(ctx.accounts.vault.xft_id == xft_id, VaultError::Unauthorized);
+        let unclaimed = ctx.accounts.vault.claimable_lamports;
+        if unclaimed > 0 {
+            let claimant = ctx.accounts.previous_claimant.as_ref().ok_or(VaultError::UnclaimedContents)?;
+            require!(claimant.key() == ctx.accounts.vault.previous_owner, VaultError::UnclaimedContents);
+            **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= unclaimed;
+            **claimant.try_borrow_mut_lamports()? += unclaimed;
+            emit!(PreviousContentsClaimed {
+                xft_id,
+                previous_owner: claimant.key(),
+                amount: unclaimed,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let rent = Rent::get()?.minimum_balance(vault_info.data_len());
+        let contents = vault_info.lamports().saturating_sub(rent);
+        let vault = &mut ctx.accounts.vault;
+        vault.previous_owner = previous_owner;
+        vault.claimable_lamports = contents;
+        vault.unlock_date = 0;
+        emit!(VaultHandedOver {
+            xft_id,
+            previous_owner,
+            claimable_lamports: contents,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Pays the previous owner of a reclaimed label the SOL that was in its vault at handover
+    pub fn claim_previous_contents(ctx: Context<ClaimPreviousContents>, xft_id: u64) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.vault_withdrawals, VaultError::WithdrawalsPaused);
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.xft_id == xft_id, VaultError::Unauthorized);
+        require!(vault.previous_owner == ctx.accounts.previous_owner.key(), VaultError::Unauthorized);
+        let amount = vault.claimable_lamports;
+        vault.claimable_lamports = 0;
+        **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.previous_owner.to_account_info().try_borrow_mut_lamports()? += amount;
+        emit!(PreviousContentsClaimed {
+            xft_id,
+            previous_owner: ctx.accounts.previous_owner.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Grows a vault written before previous_owner and claimable_lamports were added to
+    /// `Vault::LEN`. The appended fields start zeroed: no previous owner, nothing claimable.
+    /// Works for label and edition vaults, anyone can pay the extra rent.
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        let vault_info = ctx.accounts.vault.to_account_info();
+        require!(*vault_info.owner == crate::ID, VaultError::Unauthorized);
+        require!(vault_info.data_len() == 8 + Vault::LEGACY_LEN, VaultError::AlreadyMigrated);
+        require!(
+            vault_info.try_borrow_data()?[..8] == *Vault::DISCRIMINATOR,
+            VaultError::Unauthorized
+        );
+        let rent_needed = Rent::get()?
+            .minimum_balance(8 + Vault::LEN)
+            .saturating_sub(vault_info.lamports());
+        if rent_needed > 0 {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: vault_info.clone(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, rent_needed)?;
+        }
+        vault_info.resize(8 + Vault::LEN)?;
+        Ok(())
+    }
+
+    /// Closes the vault of a burned XFT and pays out its lamports. Only signed by
+    /// xft-minter::burn_xft, nothing may be left for a previous owner to claim. Every token
+    /// account the vault owns is passed as a remaining account and has to be empty.
+    pub fn close_vault(ctx: Context<CloseVault>, xft_id: u64) -> Result<()> {
+        require!(ctx.accounts.vault.xft_id == xft_id, VaultError::Unauthorized);
+        require_vault_empty(&ctx.accounts.vault, ctx.remaining_accounts)?;
+        emit!(VaultClosed {
+            xft_id,
+            receiver: ctx.accounts.receiver.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Closes the vault of a burned edition and pays out its lamports. Only signed by
+    /// xft-minter::burn_edition, with the same emptiness checks as close_vault.
+    pub fn close_edition_vault(ctx: Context<CloseEditionVault>, xft_id: u64, edition: u64) -> Result<()> {
+        require!(ctx.accounts.vault.xft_id == xft_id, VaultError::Unauthorized);
+        require_vault_empty(&ctx.accounts.vault, ctx.remaining_accounts)?;
+        emit!(EditionVaultClosed {
+            xft_id,
+            edition,
+            receiver: ctx.accounts.receiver.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
     pub fn withdraw_sol(ctx: Context<WithdrawSol>, xft_id: u64, amount: u64) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.vault_withdrawals, VaultError::WithdrawalsPaused);
         let authority = ctx.accounts.authority.key();
         let minter_program = ctx.accounts.minter_program.to_account_info();
         let operator_program = ctx.accounts.operator_program.to_account_info();
@@ -38,12 +199,18 @@ pub mod xft_vault {
         let now = clock.unix_timestamp;
         require!(ctx.accounts.vault.xft_id == xft_id, VaultError::Unauthorized);
         require!(ctx.accounts.vault.unlock_date < now, VaultError::WithdrawTooSoon);
+        // SOL held for the previous owner of a reclaimed label is not withdrawable
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let reserved = Rent::get()?
+            .minimum_balance(vault_info.data_len())
+            .saturating_add(ctx.accounts.vault.claimable_lamports);
+        require!(vault_info.lamports().saturating_sub(reserved) >= amount, VaultError::WithdrawTooMuch);
         // Only perform owner/operator checks if xft_type != 7 and != 8
         if ctx.accounts.vault.xft_type != 7 && ctx.accounts.vault.xft_type != 8 {
             // CPI: is_label_owner
             let is_label_owner_ix = minter::cpi::accounts::IsLabelOwner {
                 authority: ctx.accounts.authority.to_account_info(),
-                label_account: ctx.accounts.vault.to_account_info(), // placeholder, replace with actual label account if needed
+                label_account: ctx.accounts.label_account.to_account_info(),
             };
             let is_label_owner_ctx = CpiContext::new(minter_program.clone(), is_label_owner_ix);
             let is_owner = minter::cpi::is_label_owner(is_label_owner_ctx, authority, xft_id)?.get();
@@ -90,6 +257,8 @@ pub mod xft_vault {
     }
 
        pub fn withdraw_spl(ctx: Context<WithdrawSpl>, xft_id: u64) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.vault_withdrawals, VaultError::WithdrawalsPaused);
         let authority = ctx.accounts.authority.key();
         let minter_program = ctx.accounts.minter_program.to_account_info();
         let clock = Clock::get()?;
@@ -101,7 +270,7 @@ pub mod xft_vault {
         // CPI: is_label_owner
         let is_label_owner_ix = minter::cpi::accounts::IsLabelOwner {
             authority: ctx.accounts.authority.to_account_info(),
-            label_account: ctx.accounts.vault.to_account_info(), // placeholder
+            label_account: ctx.accounts.label_account.to_account_info(),
         };
         let is_label_owner_ctx = CpiContext::new(minter_program, is_label_owner_ix);
         let is_owner = minter::cpi::is_label_owner(is_label_owner_ctx, authority, xft_id)?.get();
@@ -112,6 +281,8 @@ pub mod xft_vault {
     }
     
     pub fn withdraw_xft(ctx: Context<WithdrawXft>, xft_id: u64) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.vault_withdrawals, VaultError::WithdrawalsPaused);
         let authority = ctx.accounts.authority.key();
         let minter_program = ctx.accounts.minter_program.to_account_info();
         let clock = Clock::get()?;
@@ -123,7 +294,7 @@ pub mod xft_vault {
             // CPI: is_label_owner
             let is_label_owner_ix = minter::cpi::accounts::IsLabelOwner {
                 authority: ctx.accounts.authority.to_account_info(),
-                label_account: ctx.accounts.vault.to_account_info(), // placeholder
+                label_account: ctx.accounts.label_account.to_account_info(),
             };
             let is_label_owner_ctx = CpiContext::new(minter_program, is_label_owner_ix);
             is_owner = minter::cpi::is_label_owner(is_label_owner_ctx, authority, xft_id)?.get();
@@ -135,6 +306,8 @@ pub mod xft_vault {
     }
     
     pub fn lock_vault(ctx: Context<LockVault>, xft_id: u64, unlock_date: i64) -> Result<()> {
+        let pause = PauseRegistry::load(&ctx.accounts.pause_registry)?;
+        require!(!pause.global && !pause.vault_withdrawals, VaultError::WithdrawalsPaused);
         let authority = ctx.accounts.authority.key();
         let minter_program = ctx.accounts.minter_program.to_account_info();
         // Only perform owner check if xft_type != 7 and != 8
@@ -142,7 +315,7 @@ pub mod xft_vault {
             // CPI: is_label_owner
             let is_label_owner_ix = minter::cpi::accounts::IsLabelOwner {
             authority: ctx.accounts.authority.to_account_info(),
-            label_account: ctx.accounts.vault.to_account_info(), // placeholder
+            label_account: ctx.accounts.label_account.to_account_info(),
             };
             let is_label_owner_ctx = CpiContext::new(minter_program, is_label_owner_ix);
             let is_owner = minter::cpi::is_label_owner(is_label_owner_ctx, authority, xft_id)?.get();
@@ -161,7 +334,15 @@ pub struct Vault {
     pub xft_id: u64,
     pub xft_type: u64, // Added field
     pub unlock_date: i64,
-    // Add more fields as needed
+    /// Owner before the label was reclaimed, entitled to claimable_lamports
+    pub previous_owner: Pubkey,
+    pub claimable_lamports: u64,
+}
+
+impl Vault {
+    pub const LEN: usize = 8 + 8 + 8 + 32 + 8;
+    /// xft_id, xft_type and unlock_date only, before previous_owner and claimable_lamports
+    pub const LEGACY_LEN: usize = 8 + 8 + 8;
 }
 
 #[derive(Accounts)]
@@ -173,7 +354,7 @@ pub struct CreateVault<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 8 + 8,
+        space = 8 + Vault::LEN,
         seeds = [b"vault", xft_id.to_le_bytes().as_ref()],
         bump
     )]
@@ -185,6 +366,144 @@ pub struct CreateVault<'info> {
     pub minter_program: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+#[instruction(xft_id: u64, edition: u64)]
+pub struct CreateEditionVault<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Vault::LEN,
+        seeds = [b"edition_vault", xft_id.to_le_bytes().as_ref(), edition.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// xft-minter vault authority PDA, only signed by xft-minter::print_edition
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+        seeds::program = minter::ID
+    )]
+    pub minter_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64, edition: u64)]
+pub struct WithdrawEditionSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"edition_vault", xft_id.to_le_bytes().as_ref(), edition.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: xft-minter Edition PDA for (xft_id, edition), read in Edition::load
+    #[account(
+        seeds = [b"edition", xft_id.to_le_bytes().as_ref(), edition.to_le_bytes().as_ref()],
+        bump,
+        seeds::program = minter::ID
+    )]
+    pub edition_record: AccountInfo<'info>,
+    /// authority's token account holding the edition, checked against the edition record mint
+    pub edition_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64)]
+pub struct HandOverVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", xft_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    /// xft-minter vault authority PDA, only signed by xft-minter::reclaim_label
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+        seeds::program = minter::ID
+    )]
+    pub minter_authority: Signer<'info>,
+    /// CHECK: previous_owner of an earlier handover, paid its unclaimed contents; omit when
+    /// nothing is claimable
+    #[account(mut)]
+    pub previous_claimant: Option<AccountInfo<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    /// CHECK: label or edition vault in the legacy layout, read manually in migrate_vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64)]
+pub struct CloseVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", xft_id.to_le_bytes().as_ref()],
+        bump,
+        close = receiver
+    )]
+    pub vault: Account<'info, Vault>,
+    /// xft-minter vault authority PDA, only signed by xft-minter::burn_xft
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+        seeds::program = minter::ID
+    )]
+    pub minter_authority: Signer<'info>,
+    /// CHECK: receives the vault rent, chosen by xft-minter
+    #[account(mut)]
+    pub receiver: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64, edition: u64)]
+pub struct CloseEditionVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"edition_vault", xft_id.to_le_bytes().as_ref(), edition.to_le_bytes().as_ref()],
+        bump,
+        close = receiver
+    )]
+    pub vault: Account<'info, Vault>,
+    /// xft-minter vault authority PDA, only signed by xft-minter::burn_edition
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+        seeds::program = minter::ID
+    )]
+    pub minter_authority: Signer<'info>,
+    /// CHECK: receives the vault rent, chosen by xft-minter
+    #[account(mut)]
+    pub receiver: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(xft_id: u64)]
+pub struct ClaimPreviousContents<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", xft_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub previous_owner: Signer<'info>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
+}
+
 // Add #[derive(Accounts)] with #[instruction(xft_id: u64)] for PDA seeds
 #[derive(Accounts)]
 #[instruction(xft_id: u64)]
@@ -197,11 +516,15 @@ pub struct WithdrawSol<'info> {
     pub vault: Account<'info, Vault>,
     #[account(mut)]
     pub authority: Signer<'info>,
-    /// CHECK: This is the xft-minter program, used for CPI only, not deserialized
-    pub minter_program: AccountInfo<'info>,
+    pub minter_program: Program<'info, Minter>,
+    /// CHECK: xft-minter XftAccount PDA for xft_id, ownership verified by minter::is_label_owner
+    #[account(seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump, seeds::program = minter::ID)]
+    pub label_account: AccountInfo<'info>,
     /// CHECK: This is the xft-operator program, used for CPI only, not deserialized
     pub operator_program: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
@@ -215,8 +538,12 @@ pub struct WithdrawSpl<'info> {
     pub vault: Account<'info, Vault>,
     #[account(mut)]
     pub authority: Signer<'info>,
-    /// CHECK: This is the xft-minter program, used for CPI only, not deserialized
-    pub minter_program: AccountInfo<'info>,
+    pub minter_program: Program<'info, Minter>,
+    /// CHECK: xft-minter XftAccount PDA for xft_id, ownership verified by minter::is_label_owner
+    #[account(seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump, seeds::program = minter::ID)]
+    pub label_account: AccountInfo<'info>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
     // Add token accounts as needed
 }
 
@@ -231,8 +558,12 @@ pub struct WithdrawXft<'info> {
     pub vault: Account<'info, Vault>,
     #[account(mut)]
     pub authority: Signer<'info>,
-    /// CHECK: This is the xft-minter program, used for CPI only, not deserialized
-    pub minter_program: AccountInfo<'info>,
+    pub minter_program: Program<'info, Minter>,
+    /// CHECK: xft-minter XftAccount PDA for xft_id, ownership verified by minter::is_label_owner
+    #[account(seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump, seeds::program = minter::ID)]
+    pub label_account: AccountInfo<'info>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
     // Add XFT token accounts as needed
 }
 
@@ -247,8 +578,12 @@ pub struct LockVault<'info> {
     pub vault: Account<'info, Vault>,
     #[account(mut)]
     pub authority: Signer<'info>,
-    /// CHECK: This is the xft-minter program, used for CPI only, not deserialized
-    pub minter_program: AccountInfo<'info>,
+    pub minter_program: Program<'info, Minter>,
+    /// CHECK: xft-minter XftAccount PDA for xft_id, ownership verified by minter::is_label_owner
+    #[account(seeds = [b"xft", xft_id.to_le_bytes().as_ref()], bump, seeds::program = minter::ID)]
+    pub label_account: AccountInfo<'info>,
+    /// CHECK: xft-admin pause registry PDA, checked in PauseRegistry::load
+    pub pause_registry: AccountInfo<'info>,
 }
 
 // Add OperatorAccount struct for operator checks
@@ -264,6 +599,68 @@ impl OperatorAccount {
     }
 }
 
+// Local mirror of admin_xft::PauseRegistry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct PauseRegistry {
+    pub global: bool,
+    pub minting: bool,
+    pub transfers: bool,
+    pub market: bool,
+    pub vault_withdrawals: bool,
+    pub bump: u8,
+}
+impl PauseRegistry {
+    /// Loads the xft-admin pause registry; an uninitialized registry means nothing is paused
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        let (expected, _) = Pubkey::find_program_address(&[b"pause_registry"], &admin_xft::ID);
+        require!(account.key() == expected, VaultError::InvalidPauseRegistry);
+        if account.data_is_empty() {
+            return Ok(PauseRegistry::default());
+        }
+        require!(*account.owner == admin_xft::ID, VaultError::InvalidPauseRegistry);
+        let data = account.try_borrow_data()?;
+        PauseRegistry::deserialize(&mut &data[8..])
+            .map_err(|_| error!(VaultError::InvalidPauseRegistry))
+    }
+}
+
+// Local mirror of minter::Edition
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct Edition {
+    pub xft_id: u64,
+    pub number: u64,
+    pub max_supply: u64,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub bump: u8,
+}
+
+impl Edition {
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        require!(*account.owner == minter::ID, VaultError::Unauthorized);
+        let data = account.try_borrow_data()?;
+        Edition::deserialize(&mut &data[8..]).map_err(|_| error!(VaultError::Unauthorized))
+    }
+}
+
+// A vault can only be closed when no previous owner has contents left to claim and every
+// token account it owns, passed in `token_accounts`, is empty. Its lamports go to the
+// receiver with the rent, so a donation cannot keep it open.
+fn require_vault_empty(vault: &Account<Vault>, token_accounts: &[AccountInfo]) -> Result<()> {
+    let vault_info = vault.to_account_info();
+    require!(vault.claimable_lamports == 0, VaultError::VaultNotEmpty);
+    for token_account_info in token_accounts {
+        require!(
+            *token_account_info.owner == anchor_spl::token::ID || *token_account_info.owner == anchor_spl::token_2022::ID,
+            VaultError::InvalidTokenAccount
+        );
+        let token_account = TokenAccount::try_deserialize(&mut &token_account_info.try_borrow_data()?[..])?;
+        require!(token_account.owner == vault_info.key(), VaultError::InvalidTokenAccount);
+        require!(token_account.amount == 0, VaultError::VaultNotEmpty);
+    }
+    Ok(())
+}
+
 fn process_sol_transfer(ctx: &Context<WithdrawSol>, amount: u64) -> Result<()> {
     let vault_account_info = ctx.accounts.vault.to_account_info();
     let authority_account_info = ctx.accounts.authority.to_account_info();
@@ -292,4 +689,55 @@ pub enum VaultError {
     WithdrawTooSoon,
     #[msg("Withdraw amount too high")] 
     WithdrawTooMuch,
+    #[msg("Invalid pause registry account")]
+    InvalidPauseRegistry,
+    #[msg("Vault withdrawals are paused")]
+    WithdrawalsPaused,
+    #[msg("Previous owner has not claimed the vault contents yet")]
+    UnclaimedContents,
+    #[msg("Vault still holds funds")]
+    VaultNotEmpty,
+    #[msg("Token account is not owned by the vault")]
+    InvalidTokenAccount,
+    #[msg("Vault already has the current layout")]
+    AlreadyMigrated,
+}
+
+#[event]
+pub struct VaultHandedOver {
+    pub xft_id: u64,
+    pub previous_owner: Pubkey,
+    pub claimable_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EditionVaultCreated {
+    pub xft_id: u64,
+    pub edition: u64,
+    pub vault: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultClosed {
+    pub xft_id: u64,
+    pub receiver: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EditionVaultClosed {
+    pub xft_id: u64,
+    pub edition: u64,
+    pub receiver: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PreviousContentsClaimed {
+    pub xft_id: u64,
+    pub previous_owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
 }